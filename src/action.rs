@@ -0,0 +1,324 @@
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::collate;
+use crate::i18n::t;
+
+/// Something the main view does, run by its keys or from the command palette.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    NewEntry,
+    QuickAdd,
+    OpenToday,
+    Review,
+    Open,
+    Delete,
+    TogglePin,
+    ToggleBillable,
+    CopyShortId,
+    CopyId,
+    ToggleCurrent,
+    FollowUp,
+    Undo,
+    Save,
+    Down,
+    Up,
+    Top,
+    Bottom,
+    CycleDensity,
+    ToggleSort,
+    CyclePeriod,
+    PreviousPeriod,
+    NextPeriod,
+    Search,
+    FilterPerson,
+    ClearSearch,
+    Statistics,
+    Week,
+    ExportWeek,
+    PreviousView,
+    Palette,
+    Quit,
+}
+
+impl Action {
+    /// Whether the action changes the log, which safe mode does not allow.
+    pub fn writes(self) -> bool {
+        matches!(
+            self,
+            Self::NewEntry
+                | Self::QuickAdd
+                | Self::OpenToday
+                | Self::Review
+                | Self::Open
+                | Self::Delete
+                | Self::TogglePin
+                | Self::ToggleBillable
+                | Self::ToggleCurrent
+                | Self::FollowUp
+                | Self::Undo
+        )
+    }
+}
+
+/// A key bound to an action, matched regardless of `<Shift>` so `<G>` is just `G`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Key {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl Key {
+    pub fn matches(self, event: KeyEvent) -> bool {
+        event.code == self.code && event.modifiers.difference(KeyModifiers::SHIFT) == self.modifiers
+    }
+}
+
+const fn key(c: char) -> Key {
+    special(KeyCode::Char(c))
+}
+
+const fn ctrl(c: char) -> Key {
+    Key {
+        code: KeyCode::Char(c),
+        modifiers: KeyModifiers::CONTROL,
+    }
+}
+
+const fn special(code: KeyCode) -> Key {
+    Key {
+        code,
+        modifiers: KeyModifiers::NONE,
+    }
+}
+
+/// An action with the keys as the palette shows them, the keys matched in the main view and
+/// what it does.
+#[derive(Debug)]
+pub struct Binding {
+    pub label: &'static str,
+    pub keys: &'static [Key],
+    pub description: &'static str,
+    pub action: Action,
+}
+
+const fn bind(
+    label: &'static str,
+    keys: &'static [Key],
+    description: &'static str,
+    action: Action,
+) -> Binding {
+    Binding {
+        label,
+        keys,
+        description,
+        action,
+    }
+}
+
+/// Every action of the main view, in the order of the palette. The main view runs the action
+/// of the first binding with a key that matches.
+pub static BINDINGS: &[Binding] = &[
+    bind("<o>", &[key('o')], "New entry", Action::NewEntry),
+    bind("<i>", &[key('i')], "Quick add", Action::QuickAdd),
+    bind("<t>", &[key('t')], "Open today's note", Action::OpenToday),
+    bind("<R>", &[key('R')], "Review today's entries", Action::Review),
+    bind(
+        "<e> | <Enter> | <Space>",
+        &[key('e'), special(KeyCode::Enter), key(' ')],
+        "Open selected entry",
+        Action::Open,
+    ),
+    bind(
+        "<d> <d>",
+        &[key('d')],
+        "Delete selected entry",
+        Action::Delete,
+    ),
+    bind(
+        "<p>",
+        &[key('p')],
+        "Pin or unpin selected entry",
+        Action::TogglePin,
+    ),
+    bind(
+        "<b>",
+        &[key('b')],
+        "Mark selected entry as billable or not",
+        Action::ToggleBillable,
+    ),
+    bind(
+        "<y>",
+        &[key('y')],
+        "Copy the short id of selected entry",
+        Action::CopyShortId,
+    ),
+    bind(
+        "<Y>",
+        &[key('Y')],
+        "Copy the full id of selected entry",
+        Action::CopyId,
+    ),
+    bind(
+        "<c>",
+        &[key('c')],
+        "Start or stop working on selected entry",
+        Action::ToggleCurrent,
+    ),
+    bind(
+        "<f>",
+        &[key('f')],
+        "Follow up on selected entry",
+        Action::FollowUp,
+    ),
+    bind(
+        "<u>",
+        &[key('u')],
+        "Undo last deletion or edit",
+        Action::Undo,
+    ),
+    bind("<CTRL-s>", &[ctrl('s')], "Save all changes", Action::Save),
+    bind(
+        "<j> | <Down>",
+        &[key('j'), special(KeyCode::Down)],
+        "Down",
+        Action::Down,
+    ),
+    bind(
+        "<k> | <Up>",
+        &[key('k'), special(KeyCode::Up)],
+        "Up",
+        Action::Up,
+    ),
+    bind(
+        "<g> | <Home>",
+        &[key('g'), special(KeyCode::Home)],
+        "Top",
+        Action::Top,
+    ),
+    bind(
+        "<G> | <End>",
+        &[key('G'), special(KeyCode::End)],
+        "Bottom",
+        Action::Bottom,
+    ),
+    bind(
+        "<z>",
+        &[key('z')],
+        "Cycle row density",
+        Action::CycleDensity,
+    ),
+    bind(
+        "<S>",
+        &[key('S')],
+        "Toggle sorting by date or alphabetically",
+        Action::ToggleSort,
+    ),
+    bind(
+        "<v>",
+        &[key('v')],
+        "Show a month, week, day or everything",
+        Action::CyclePeriod,
+    ),
+    bind(
+        "<[>",
+        &[key('[')],
+        "Show the previous period",
+        Action::PreviousPeriod,
+    ),
+    bind(
+        "<]>",
+        &[key(']')],
+        "Show the next period",
+        Action::NextPeriod,
+    ),
+    bind(
+        "</>",
+        &[key('/')],
+        "Search, e.g. tag:foo project:acme /deploy.*prod/ before:2024-06-01",
+        Action::Search,
+    ),
+    bind("<@>", &[key('@')], "Filter by person", Action::FilterPerson),
+    bind(
+        "<ESC>",
+        &[special(KeyCode::Esc)],
+        "Clear the search",
+        Action::ClearSearch,
+    ),
+    bind("<s>", &[key('s')], "Statistics", Action::Statistics),
+    bind("<w>", &[key('w')], "Week view", Action::Week),
+    bind("", &[], "Export this week as HTML", Action::ExportWeek),
+    bind(
+        "<CTRL-^>",
+        &[ctrl('^'), ctrl('6')],
+        "Switch to the previous view",
+        Action::PreviousView,
+    ),
+    bind(
+        "<CTRL-p> | <:>",
+        &[ctrl('p'), key(':')],
+        "Command palette",
+        Action::Palette,
+    ),
+    bind(
+        "<q> | <ESC>",
+        &[key('q'), special(KeyCode::Esc)],
+        "Quit",
+        Action::Quit,
+    ),
+];
+
+/// The action bound to `event` in the main view. `<ESC>` clears the search while there is
+/// one, and quits otherwise.
+pub fn find(event: KeyEvent, searching: bool) -> Option<Action> {
+    BINDINGS
+        .iter()
+        .filter(|binding| binding.action != Action::ClearSearch || searching)
+        .find(|binding| binding.keys.iter().any(|key| key.matches(event)))
+        .map(|binding| binding.action)
+}
+
+/// The command palette, listing the actions whose description matches what was typed.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Palette {
+    pub input: String,
+    pub selected: usize,
+}
+
+impl Palette {
+    /// The bindings offered for the input, best match first. Actions that would change the
+    /// log are left out when it is `read_only`.
+    pub fn matches(&self, read_only: bool) -> Vec<&'static Binding> {
+        let mut matches: Vec<(i64, &Binding)> = BINDINGS
+            .iter()
+            .filter(|binding| {
+                binding.action != Action::Palette && !(read_only && binding.action.writes())
+            })
+            .filter_map(|binding| Some((score(t(binding.description), &self.input)?, binding)))
+            .collect();
+        matches.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+        matches.into_iter().map(|(_, binding)| binding).collect()
+    }
+}
+
+/// How well `query` matches `text` when its characters appear in order, ignoring case and
+/// accents: matches at the start of a word and runs of matches count more. `None` if they do
+/// not all appear.
+fn score(text: &str, query: &str) -> Option<i64> {
+    let text: Vec<char> = collate::key(text).chars().collect();
+    let mut score = 0;
+    let mut at = 0;
+    let mut previous: Option<usize> = None;
+    for c in collate::key(query).chars().filter(|c| !c.is_whitespace()) {
+        let found = at + text[at..].iter().position(|t| *t == c)?;
+        score += 1;
+        if found == 0 || !text[found - 1].is_alphanumeric() {
+            score += 5;
+        }
+        if previous.is_some_and(|previous| previous + 1 == found) {
+            score += 3;
+        }
+        previous = Some(found);
+        at = found + 1;
+    }
+    Some(score)
+}
@@ -0,0 +1,102 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{self, Stdio};
+
+use color_eyre::{Result, eyre::eyre};
+use serde::{Deserialize, Serialize};
+
+use crate::App;
+
+/// Largest file copied into the attachments folder, bigger ones are only referenced.
+pub const MAX_SIZE: u64 = 10 * 1024 * 1024;
+
+/// A file kept with an entry.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum Attachment {
+    /// A file elsewhere on disk, by its absolute path.
+    Link(PathBuf),
+    /// A copy in the attachments folder of the entry, by its file name there.
+    File(String),
+}
+
+impl Attachment {
+    /// References `path` without copying it.
+    pub fn link(path: &Path) -> Result<Self> {
+        let path = path
+            .canonicalize()
+            .map_err(|e| eyre!("cannot attach {}: {e}", path.display()))?;
+        Ok(Self::Link(path))
+    }
+
+    /// Copies `path` into the attachments folder of the entry `id`.
+    pub fn store(id: &str, path: &Path) -> Result<Self> {
+        let size = fs::metadata(path)
+            .map_err(|e| eyre!("cannot attach {}: {e}", path.display()))?
+            .len();
+        if size > MAX_SIZE {
+            return Err(eyre!(
+                "{} is larger than {} MB, attach it without --copy to keep a reference instead",
+                path.display(),
+                MAX_SIZE / 1024 / 1024
+            ));
+        }
+        let name = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .ok_or_else(|| eyre!("cannot attach {}: not a file", path.display()))?
+            .to_owned();
+        let target = dir(id).join(&name);
+        if target.exists() {
+            return Err(eyre!("a file named {name} is already attached"));
+        }
+        fs::create_dir_all(dir(id))?;
+        fs::copy(path, target)?;
+        Ok(Self::File(name))
+    }
+
+    /// The name shown in the editor.
+    pub fn name(&self) -> String {
+        match self {
+            Self::Link(path) => path.display().to_string(),
+            Self::File(name) => name.clone(),
+        }
+    }
+
+    /// Where the file is on disk for the entry `id`.
+    pub fn path(&self, id: &str) -> PathBuf {
+        match self {
+            Self::Link(path) => path.clone(),
+            Self::File(name) => dir(id).join(name),
+        }
+    }
+}
+
+/// The folder copies attached to the entry `id` are kept in.
+pub fn dir(id: &str) -> PathBuf {
+    App::config_path().with_file_name("attachments").join(id)
+}
+
+/// Opens `path` with the default application of the desktop, without waiting for it.
+pub fn open(path: &Path) -> Result<()> {
+    if !path.exists() {
+        return Err(eyre!("{} does not exist anymore", path.display()));
+    }
+    let mut command = if cfg!(windows) {
+        let mut command = process::Command::new("cmd");
+        command.args(["/C", "start", ""]);
+        command
+    } else if cfg!(target_os = "macos") {
+        process::Command::new("open")
+    } else {
+        process::Command::new("xdg-open")
+    };
+    command
+        .arg(path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| eyre!("failed to open {}: {e}", path.display()))?;
+    Ok(())
+}
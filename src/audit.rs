@@ -0,0 +1,211 @@
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+
+use chrono::{DateTime, Local};
+use color_eyre::{Result, eyre::eyre};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::App;
+use crate::log::Item;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Kind {
+    Add,
+    Update,
+    Delete,
+    /// Taken over from another copy of the log, see `lw merge` and `lw sync`.
+    Merge,
+    /// Edited in or added from the files of `lw export obsidian --watch`.
+    Import,
+    Undo,
+}
+
+impl Kind {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Add => "add",
+            Self::Update => "update",
+            Self::Delete => "delete",
+            Self::Merge => "merge",
+            Self::Import => "import",
+            Self::Undo => "undo",
+        }
+    }
+}
+
+/// A change of an entry as kept in the audit log, `None` before it was added or after it was
+/// deleted.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Record {
+    pub at: DateTime<Local>,
+    pub kind: Kind,
+    pub id: Uuid,
+    pub before: Option<Item>,
+    pub after: Option<Item>,
+}
+
+impl Record {
+    /// The record as `lw history` prints it: when, what and the id, then the lines of the
+    /// content that changed and the names of the other changed attributes.
+    pub fn render(&self, abbrev: usize) -> String {
+        let mut id = self.id.simple().to_string();
+        id.truncate(abbrev);
+        let mut out = format!(
+            "{} {:<6} {id}\n",
+            self.at.format("%Y-%m-%d %H:%M:%S"),
+            self.kind.name()
+        );
+        let content = |item: &Option<Item>| item.as_ref().map(Item::content).unwrap_or_default();
+        let (before, after) = (content(&self.before), content(&self.after));
+        if before != after {
+            for line in before.lines() {
+                let _ = writeln!(out, "  - {line}");
+            }
+            for line in after.lines() {
+                let _ = writeln!(out, "  + {line}");
+            }
+        }
+        if let (Some(before), Some(after)) = (&self.before, &self.after) {
+            let changed = changed(before, after);
+            if !changed.is_empty() {
+                let _ = writeln!(out, "  changed: {}", changed.join(", "));
+            }
+        }
+        out
+    }
+}
+
+/// Attributes other than the content and the modification time that differ.
+fn changed(before: &Item, after: &Item) -> Vec<String> {
+    let (Ok(serde_json::Value::Object(before)), Ok(serde_json::Value::Object(after))) =
+        (serde_json::to_value(before), serde_json::to_value(after))
+    else {
+        return Vec::new();
+    };
+    after
+        .iter()
+        .filter(|(key, value)| {
+            !matches!(key.as_str(), "content" | "modified") && before.get(*key) != Some(value)
+        })
+        .map(|(key, _)| key.clone())
+        .collect()
+}
+
+/// The entries changed since the last save, each as it was before the first of its changes.
+#[derive(Debug, Default, Clone)]
+pub struct Trail {
+    pending: Vec<(DateTime<Local>, Kind, Uuid, Option<Item>)>,
+    positions: HashMap<Uuid, usize>,
+    /// Records that could not be appended, written ahead of the next ones.
+    unwritten: Vec<Record>,
+    /// Recorded instead of the kind of each change while set, e.g. during an import.
+    pub context: Option<Kind>,
+}
+
+impl Trail {
+    /// Notes that the entry `id`, currently `before`, is about to change, unless it changed
+    /// since the last save already.
+    pub fn touch(&mut self, kind: Kind, id: Uuid, before: Option<&Item>) {
+        if self.positions.contains_key(&id) {
+            return;
+        }
+        self.positions.insert(id, self.pending.len());
+        let kind = self.context.unwrap_or(kind);
+        self.pending.push((Local::now(), kind, id, before.cloned()));
+    }
+
+    /// The records of the pending changes, with the entries as they are in `logs` now, after
+    /// the ones that could not be written before. Entries that ended up as they were are left
+    /// out.
+    pub fn take(&mut self, logs: &[Item]) -> Vec<Record> {
+        let mut after: Vec<Option<Item>> = vec![None; self.pending.len()];
+        for item in logs {
+            if let Some(&position) = self.positions.get(&item.uuid()) {
+                after[position] = Some(item.clone());
+            }
+        }
+        self.positions.clear();
+        let mut records = std::mem::take(&mut self.unwritten);
+        let pending = std::mem::take(&mut self.pending)
+            .into_iter()
+            .zip(after)
+            .filter(|((_, _, _, before), after)| before != after)
+            .map(|((at, kind, id, before), after)| {
+                let kind = match kind {
+                    Kind::Add | Kind::Update | Kind::Delete if before.is_none() => Kind::Add,
+                    Kind::Add | Kind::Update | Kind::Delete if after.is_none() => Kind::Delete,
+                    Kind::Add | Kind::Update | Kind::Delete => Kind::Update,
+                    kind => kind,
+                };
+                Record {
+                    at,
+                    kind,
+                    id,
+                    before,
+                    after,
+                }
+            });
+        records.extend(pending);
+        records
+    }
+
+    /// Keeps `records` that failed to be appended for the next save.
+    pub fn keep(&mut self, records: Vec<Record>) {
+        self.unwritten = records;
+    }
+}
+
+pub fn path() -> PathBuf {
+    App::config_path().with_file_name("audit.jsonl")
+}
+
+/// Appends `records` to the audit log, one JSON object per line.
+pub fn append(records: &[Record]) -> Result<()> {
+    if records.is_empty() {
+        return Ok(());
+    }
+    let mut lines = Vec::new();
+    for record in records {
+        serde_json::to_writer(&mut lines, record)?;
+        lines.push(b'\n');
+    }
+    let path = path();
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .and_then(|mut file| file.write_all(&lines))
+        .map_err(|e| eyre!("failed to write {}: {e}", path.display()))
+}
+
+/// The records of the audit log, oldest first, of the entries whose id starts with `id` if
+/// given.
+pub fn read(id: Option<&str>) -> Result<Vec<Record>> {
+    let path = path();
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(eyre!("failed to read {}: {e}", path.display())),
+    };
+    let prefix = id.map(|id| id.replace('-', "").to_lowercase());
+    let mut records = Vec::new();
+    for (number, line) in content.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let record: Record = serde_json::from_str(line)
+            .map_err(|e| eyre!("{}:{}: {e}", path.display(), number + 1))?;
+        if prefix
+            .as_ref()
+            .is_none_or(|prefix| record.id.simple().to_string().starts_with(prefix.as_str()))
+        {
+            records.push(record);
+        }
+    }
+    Ok(records)
+}
@@ -0,0 +1,84 @@
+use std::collections::BTreeSet;
+use std::{fs, path::PathBuf};
+
+use chrono::{Datelike, NaiveDateTime};
+use color_eyre::Result;
+
+use crate::settings::Backups;
+use crate::{App, storage};
+
+static PREFIX: &str = "config-";
+static FORMAT: &str = "%Y%m%d-%H%M%S";
+
+/// Identifies the day, week or month a snapshot belongs to.
+type Period = fn(&NaiveDateTime) -> (i32, u32);
+
+pub fn dir() -> PathBuf {
+    App::config_path().with_file_name("backups")
+}
+
+/// Writes `data`, the whole log as a single data file, into the backups directory unless it is
+/// unchanged since the latest snapshot, and prunes snapshots according to `policy`.
+pub fn snapshot(data: &[u8], policy: &Backups) -> Result<()> {
+    let dir = dir();
+    fs::create_dir_all(&dir)?;
+    let snapshots = list()?;
+    let unchanged = snapshots
+        .first()
+        .is_some_and(|(_, latest)| fs::read(latest).is_ok_and(|l| l == data));
+    if !unchanged {
+        let name = format!("{PREFIX}{}.json", chrono::Local::now().format(FORMAT));
+        storage::replace(&dir.join(name), data)?;
+    }
+    for path in expired(list()?, policy) {
+        fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
+/// Snapshots in the backups directory, newest first.
+fn list() -> Result<Vec<(NaiveDateTime, PathBuf)>> {
+    let mut snapshots: Vec<(NaiveDateTime, PathBuf)> = fs::read_dir(dir())?
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            let stem = path.file_stem()?.to_str()?.strip_prefix(PREFIX)?;
+            let at = NaiveDateTime::parse_from_str(stem, FORMAT).ok()?;
+            Some((at, path))
+        })
+        .collect();
+    snapshots.sort_by_key(|(at, _)| std::cmp::Reverse(*at));
+    Ok(snapshots)
+}
+
+/// Snapshots not selected by any tier of `policy`. `snapshots` must be sorted newest first.
+fn expired(snapshots: Vec<(NaiveDateTime, PathBuf)>, policy: &Backups) -> Vec<PathBuf> {
+    let mut keep = BTreeSet::new();
+    keep.extend(0..policy.keep_last.min(snapshots.len()));
+
+    let tiers: [(usize, Period); 3] = [
+        (policy.daily, |at| (at.year(), at.ordinal())),
+        (policy.weekly, |at| {
+            let week = at.iso_week();
+            (week.year(), week.week())
+        }),
+        (policy.monthly, |at| (at.year(), at.month())),
+    ];
+    for (count, period) in tiers {
+        let mut seen = BTreeSet::new();
+        for (i, (at, _)) in snapshots.iter().enumerate() {
+            if seen.len() == count {
+                break;
+            }
+            if seen.insert(period(at)) {
+                keep.insert(i);
+            }
+        }
+    }
+
+    snapshots
+        .into_iter()
+        .enumerate()
+        .filter(|(i, _)| !keep.contains(i))
+        .map(|(_, (_, path))| path)
+        .collect()
+}
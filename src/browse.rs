@@ -0,0 +1,99 @@
+use chrono::{Datelike, Days, Months, NaiveDate};
+
+use crate::{i18n, shard, week};
+
+/// How much of the log the table shows at once, cycled with `<v>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Span {
+    Day,
+    Week,
+    Month,
+}
+
+/// The day, week or month the table is limited to, stepped through with `<[>` and `<]>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Period {
+    pub span: Span,
+    /// First day of the period.
+    pub start: NaiveDate,
+}
+
+impl Period {
+    /// The period of `span` that contains `day`.
+    pub fn containing(span: Span, day: NaiveDate) -> Self {
+        let start = match span {
+            Span::Day => day,
+            Span::Week => week::Cursor::on(day).start,
+            Span::Month => day.with_day(1).unwrap_or(day),
+        };
+        Self { span, start }
+    }
+
+    /// The first day after the period.
+    pub fn end(self) -> NaiveDate {
+        match self.span {
+            Span::Day => self.start + Days::new(1),
+            Span::Week => self.start + Days::new(7),
+            Span::Month => self.start + Months::new(1),
+        }
+    }
+
+    pub fn contains(self, day: NaiveDate) -> bool {
+        self.start <= day && day < self.end()
+    }
+
+    /// The period `steps` periods later, or earlier for negative steps.
+    pub fn step(self, steps: i32) -> Self {
+        let start = match self.span {
+            Span::Day => self.start + chrono::Duration::days(steps.into()),
+            Span::Week => self.start + chrono::Duration::weeks(steps.into()),
+            Span::Month if steps < 0 => self.start - Months::new(steps.unsigned_abs()),
+            Span::Month => self.start + Months::new(steps.unsigned_abs()),
+        };
+        Self { start, ..self }
+    }
+
+    /// The next shorter period around `day`, or around the start if `day` is outside: a month
+    /// becomes a week, a week a day and a day the whole log, `None`.
+    pub fn narrow(self, day: NaiveDate) -> Option<Self> {
+        let day = if self.contains(day) { day } else { self.start };
+        match self.span {
+            Span::Month => Some(Self::containing(Span::Week, day)),
+            Span::Week => Some(Self::containing(Span::Day, day)),
+            Span::Day => None,
+        }
+    }
+
+    /// The shards holding the entries of the period.
+    pub fn months(self) -> Vec<String> {
+        let mut months = vec![shard::month_of(self.start)];
+        let last = shard::month_of(self.end() - Days::new(1));
+        if last != months[0] {
+            months.push(last);
+        }
+        months
+    }
+
+    /// The period as shown in the title, e.g. `Oct 2026` or `Week 42 · Oct 12 – Oct 18`.
+    pub fn title(self) -> String {
+        match self.span {
+            Span::Day => format!(
+                "{}, {}",
+                i18n::weekday(self.start.weekday()),
+                i18n::long_date(self.start)
+            ),
+            Span::Week => {
+                let cursor = week::Cursor::on(self.start);
+                i18n::fill(
+                    "Week {} · {} – {}",
+                    &[
+                        &cursor.number().to_string(),
+                        &i18n::short_date(cursor.start),
+                        &i18n::short_date(cursor.end()),
+                    ],
+                )
+            }
+            Span::Month => i18n::month(self.start),
+        }
+    }
+}
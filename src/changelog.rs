@@ -0,0 +1,51 @@
+static CHANGELOG: &str = include_str!("../CHANGELOG.md");
+
+pub static VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Version assumed for data files written before they carried a version.
+pub static UNVERSIONED: &str = "0.2.0";
+
+/// Changes to the data file format, by the version that introduced them.
+static MIGRATIONS: &[(&str, &str)] = &[
+    (
+        "0.3.0",
+        "Entries gained a `pinned` flag, and deleted entries are now remembered as tombstones so merging and syncing don't bring them back.",
+    ),
+    (
+        "0.3.0",
+        "Entries moved from `config.json` into one file per month in the `logs` directory.",
+    ),
+];
+
+fn parse(version: &str) -> Vec<u64> {
+    version
+        .trim()
+        .split('.')
+        .map(|part| part.parse().unwrap_or(0))
+        .collect()
+}
+
+/// Whether `version` is older than the running version.
+pub fn is_outdated(version: &str) -> bool {
+    parse(version) < parse(VERSION)
+}
+
+/// Changelog sections of every version released after `version`, newest first.
+pub fn since(version: &str) -> Vec<(&'static str, &'static str)> {
+    CHANGELOG
+        .split("\n## ")
+        .skip(1)
+        .filter_map(|section| section.split_once('\n'))
+        .map(|(v, notes)| (v.trim(), notes.trim()))
+        .filter(|(v, _)| parse(v) > parse(version) && parse(v) <= parse(VERSION))
+        .collect()
+}
+
+/// Data migrations applied when loading a file written by `version`.
+pub fn migrations_since(version: &str) -> Vec<&'static str> {
+    MIGRATIONS
+        .iter()
+        .filter(|(v, _)| parse(v) > parse(version) && parse(v) <= parse(VERSION))
+        .map(|(_, description)| *description)
+        .collect()
+}
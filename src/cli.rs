@@ -0,0 +1,936 @@
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::path::PathBuf;
+use std::{env, fs, process};
+
+use chrono::{DateTime, Datelike, Local, NaiveDate};
+use color_eyre::{Result, eyre::eyre};
+
+use crate::attachment::Attachment;
+use crate::dates;
+use crate::export::{self, Format, Period};
+use crate::invoice::{self, Invoice};
+use crate::log::Item;
+use crate::query::Query;
+use crate::selector::Selector;
+use crate::settings::{Compression, Settings};
+use crate::shell::Shell;
+use crate::stats::HourDistribution;
+use crate::undo::Journal;
+use crate::{Added, App};
+use crate::{audit, backup, daemon, draft, paths, popup, review, tag, vault};
+
+/// How entries are referred to in usage messages.
+static SELECTOR: &str = "id|last|n";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    Tui,
+    Add {
+        text: String,
+        /// Read one entry per non-empty line from stdin.
+        per_line: bool,
+        /// Tags and projects, with their prefix, appended to every entry.
+        markers: Vec<String>,
+        at: Option<DateTime<Local>>,
+        print_ids: bool,
+    },
+    Stats {
+        by_hour: bool,
+    },
+    Undo,
+    /// Appends the text to the daily note, or opens the note in the TUI without text.
+    Today(Option<String>),
+    List {
+        with: Vec<String>,
+        since: Option<NaiveDate>,
+    },
+    Search(Query),
+    Serve {
+        port: u16,
+    },
+    /// Keeps the log loaded to add entries sent by other invocations of `lw add`.
+    Daemon,
+    Sync,
+    Merge(PathBuf),
+    Show(Selector),
+    Edit {
+        entry: Selector,
+        /// New content, or `None` to edit the entry in `$EDITOR`.
+        text: Option<String>,
+    },
+    Remove(Selector),
+    Append {
+        entry: Selector,
+        text: String,
+    },
+    /// Sets custom fields, given as `name=value`.
+    Set {
+        entry: Selector,
+        fields: Vec<(String, String)>,
+    },
+    Attach {
+        entry: Selector,
+        paths: Vec<PathBuf>,
+        /// Copy the files into the attachments folder instead of referencing them.
+        copy: bool,
+    },
+    Compact,
+    /// Prints where lw keeps its files, without creating any.
+    Paths,
+    /// Prints the audit log, of the entries whose id starts with the prefix if given.
+    History(Option<String>),
+    /// The daily goal of the week containing the day.
+    Goals(NaiveDate),
+    Invoice {
+        project: Option<String>,
+        /// First day of the month.
+        month: NaiveDate,
+        rate: Option<f64>,
+    },
+    PostSlack(NaiveDate),
+    /// Prints the integration logging the last command of the shell.
+    ShellHook(Shell),
+    Export {
+        format: Format,
+        /// `None` to take it from the preset, or everything.
+        period: Option<Period>,
+        query: Option<Query>,
+        output: Option<PathBuf>,
+        /// Name of the `export_presets` entry filling in what is not given.
+        preset: Option<String>,
+    },
+    /// Writes a Markdown file per day into `dir`, e.g. an Obsidian vault.
+    ExportVault {
+        dir: PathBuf,
+        period: Period,
+        query: Option<Query>,
+        /// Keep running and import edits of the files.
+        watch: bool,
+    },
+}
+
+impl Command {
+    /// The command given by `args`. Anything that is not a command adds its words as a new
+    /// entry, and so does text that merely starts with the name of one, like
+    /// `lw stats were wrong`, unless it has options meant for that command.
+    pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Self> {
+        let args: Vec<String> = args.into_iter().collect();
+        match Self::parse_command(&args) {
+            Err(_) if args.len() > 1 && !args[1..].iter().any(|arg| arg.starts_with('-')) => {
+                Ok(Self::text(&args))
+            }
+            command => command,
+        }
+    }
+
+    /// An entry of `args` as `lw <text>` adds it.
+    fn text(args: &[String]) -> Self {
+        Self::Add {
+            text: args.join(" "),
+            per_line: false,
+            markers: Vec::new(),
+            at: None,
+            print_ids: false,
+        }
+    }
+
+    fn parse_command(args: &[String]) -> Result<Self> {
+        match args.first().map(String::as_str) {
+            None => Ok(Self::Tui),
+            Some("stats") => {
+                let mut by_hour = false;
+                for arg in &args[1..] {
+                    match arg.as_str() {
+                        "--by-hour" => by_hour = true,
+                        other => return Err(eyre!("unknown argument for stats: {other}")),
+                    }
+                }
+                Ok(Self::Stats { by_hour })
+            }
+            Some("list") => {
+                let mut with = Vec::new();
+                let mut since = None;
+                let mut rest = args[1..].iter();
+                while let Some(arg) = rest.next() {
+                    match arg.as_str() {
+                        "--with" => with.push(
+                            rest.next()
+                                .ok_or_else(|| eyre!("--with requires a person"))?
+                                .clone(),
+                        ),
+                        "--since" => {
+                            since = Some(dates::day(
+                                rest.next().ok_or_else(|| eyre!("--since requires a day"))?,
+                                Local::now().date_naive(),
+                            )?);
+                        }
+                        other => return Err(eyre!("unknown argument for list: {other}")),
+                    }
+                }
+                Ok(Self::List { with, since })
+            }
+            Some("search") => match &args[1..] {
+                [] => Err(eyre!("usage: lw search <query>")),
+                query => Ok(Self::Search(query.join(" ").parse()?)),
+            },
+            Some("serve") => {
+                let mut port = 8080;
+                let mut rest = args[1..].iter();
+                while let Some(arg) = rest.next() {
+                    match arg.as_str() {
+                        "--port" => {
+                            port = rest
+                                .next()
+                                .ok_or_else(|| eyre!("--port requires a value"))?
+                                .parse()?;
+                        }
+                        other => return Err(eyre!("unknown argument for serve: {other}")),
+                    }
+                }
+                Ok(Self::Serve { port })
+            }
+            Some("merge") => match &args[1..] {
+                [path] => Ok(Self::Merge(PathBuf::from(path))),
+                _ => Err(eyre!("usage: lw merge <file>")),
+            },
+            Some("export") => {
+                let vault = args.get(1).is_some_and(|arg| arg == "obsidian");
+                let mut format = Format::Html;
+                let mut period = None;
+                let mut query = None;
+                let mut output = None;
+                let mut preset = None;
+                let mut dir = None;
+                let mut watch = false;
+                let mut rest = args[if vault { 2 } else { 1 }..].iter();
+                while let Some(arg) = rest.next() {
+                    match arg.as_str() {
+                        "--dir" if vault => {
+                            dir = Some(PathBuf::from(
+                                rest.next().ok_or_else(|| eyre!("--dir requires a value"))?,
+                            ));
+                        }
+                        "--watch" if vault => watch = true,
+                        "--format" if !vault => {
+                            format = match rest.next().map(String::as_str) {
+                                Some("html") => Format::Html,
+                                Some(other) => return Err(eyre!("unknown export format: {other}")),
+                                None => return Err(eyre!("--format requires a value")),
+                            };
+                        }
+                        "--day" => period = Some(Period::Day),
+                        "--week" => period = Some(Period::Week),
+                        "--month" => period = Some(Period::Month),
+                        "--all" => period = Some(Period::All),
+                        "--since" => {
+                            period = Some(Period::Since(dates::day(
+                                rest.next().ok_or_else(|| eyre!("--since requires a day"))?,
+                                Local::now().date_naive(),
+                            )?));
+                        }
+                        "--query" | "-q" => {
+                            query = Some(
+                                rest.next()
+                                    .ok_or_else(|| eyre!("--query requires a search"))?
+                                    .parse()?,
+                            );
+                        }
+                        "--preset" if !vault => {
+                            preset = Some(
+                                rest.next()
+                                    .ok_or_else(|| eyre!("--preset requires a name"))?
+                                    .to_owned(),
+                            );
+                        }
+                        "--output" | "-o" if !vault => {
+                            output = Some(PathBuf::from(
+                                rest.next()
+                                    .ok_or_else(|| eyre!("--output requires a value"))?,
+                            ));
+                        }
+                        other => return Err(eyre!("unknown argument for export: {other}")),
+                    }
+                }
+                if vault {
+                    return Ok(Self::ExportVault {
+                        dir: dir.ok_or_else(|| {
+                            eyre!(
+                                "usage: lw export obsidian --dir <dir> [--query <search>] [--watch]"
+                            )
+                        })?,
+                        period: period.unwrap_or(Period::All),
+                        query,
+                        watch,
+                    });
+                }
+                Ok(Self::Export {
+                    format,
+                    period,
+                    query,
+                    output,
+                    preset,
+                })
+            }
+            Some("post") => {
+                if args.get(1).map(String::as_str) != Some("slack") {
+                    return Err(eyre!("usage: lw post slack [--day <day>]"));
+                }
+                let mut day = Local::now().date_naive();
+                let mut rest = args[2..].iter();
+                while let Some(arg) = rest.next() {
+                    match arg.as_str() {
+                        "--day" => {
+                            day = dates::day(
+                                rest.next().ok_or_else(|| eyre!("--day requires a value"))?,
+                                Local::now().date_naive(),
+                            )?;
+                        }
+                        other => return Err(eyre!("unknown argument for post: {other}")),
+                    }
+                }
+                Ok(Self::PostSlack(day))
+            }
+            Some("shell-hook") => match &args[1..] {
+                [shell] => Ok(Self::ShellHook(shell.parse()?)),
+                _ => Err(eyre!("usage: lw shell-hook zsh|bash|fish")),
+            },
+            Some("show") => match &args[1..] {
+                [entry] => Ok(Self::Show(entry.parse()?)),
+                _ => Err(eyre!("usage: lw show <{SELECTOR}>")),
+            },
+            Some("edit") => match &args[1..] {
+                [entry, text @ ..] => Ok(Self::Edit {
+                    entry: entry.parse()?,
+                    text: (!text.is_empty()).then(|| text.join(" ")),
+                }),
+                [] => Err(eyre!("usage: lw edit <{SELECTOR}> [text]")),
+            },
+            Some("rm") => match &args[1..] {
+                [entry] => Ok(Self::Remove(entry.parse()?)),
+                _ => Err(eyre!("usage: lw rm <{SELECTOR}>")),
+            },
+            Some("append") => match &args[1..] {
+                [entry, text @ ..] if !text.is_empty() => Ok(Self::Append {
+                    entry: entry.parse()?,
+                    text: text.join(" "),
+                }),
+                _ => Err(eyre!("usage: lw append <{SELECTOR}> <text>")),
+            },
+            Some("set") => match &args[1..] {
+                [entry, fields @ ..] if !fields.is_empty() => Ok(Self::Set {
+                    entry: entry.parse()?,
+                    fields: fields
+                        .iter()
+                        .map(|f| match f.split_once('=') {
+                            Some((name, value)) if !name.is_empty() => {
+                                Ok((name.to_owned(), value.to_owned()))
+                            }
+                            _ => Err(eyre!("expected name=value, got {f}")),
+                        })
+                        .collect::<Result<_>>()?,
+                }),
+                _ => Err(eyre!("usage: lw set <{SELECTOR}> <name>=<value>...")),
+            },
+            Some("attach") => {
+                let mut copy = false;
+                let mut rest = Vec::new();
+                for arg in &args[1..] {
+                    match arg.as_str() {
+                        "--copy" => copy = true,
+                        _ => rest.push(arg),
+                    }
+                }
+                match rest.as_slice() {
+                    [entry, paths @ ..] if !paths.is_empty() => Ok(Self::Attach {
+                        entry: entry.parse()?,
+                        paths: paths.iter().map(PathBuf::from).collect(),
+                        copy,
+                    }),
+                    _ => Err(eyre!("usage: lw attach [--copy] <{SELECTOR}> <file>...")),
+                }
+            }
+            Some("goals") => match &args[1..] {
+                [] => Ok(Self::Goals(Local::now().date_naive())),
+                [flag, day] if flag == "--week" => {
+                    Ok(Self::Goals(dates::day(day, Local::now().date_naive())?))
+                }
+                _ => Err(eyre!("usage: lw goals [--week <day>]")),
+            },
+            Some("invoice") => {
+                let mut project = None;
+                let mut month = Local::now().date_naive().with_day(1).unwrap_or_default();
+                let mut rate = None;
+                let mut rest = args[1..].iter();
+                while let Some(arg) = rest.next() {
+                    let mut value = || {
+                        rest.next()
+                            .ok_or_else(|| eyre!("{arg} requires a value"))
+                            .cloned()
+                    };
+                    match arg.as_str() {
+                        "--project" => project = Some(value()?),
+                        "--month" => month = invoice::month(&value()?)?,
+                        "--rate" => {
+                            let value = value()?;
+                            rate =
+                                Some(value.parse().map_err(|_| {
+                                    eyre!("invalid rate {value}, expected a number")
+                                })?);
+                        }
+                        other => return Err(eyre!("unknown argument for invoice: {other}")),
+                    }
+                }
+                Ok(Self::Invoice {
+                    project,
+                    month,
+                    rate,
+                })
+            }
+            Some("compact") => match args.get(1) {
+                Some(other) => Err(eyre!("unknown argument for compact: {other}")),
+                None => Ok(Self::Compact),
+            },
+            Some("history") => match &args[1..] {
+                [] => Ok(Self::History(None)),
+                [id] => Ok(Self::History(Some(id.clone()))),
+                _ => Err(eyre!("usage: lw history [id]")),
+            },
+            Some("paths") => match args.get(1) {
+                Some(other) => Err(eyre!("unknown argument for paths: {other}")),
+                None => Ok(Self::Paths),
+            },
+            Some("today") => {
+                let text = args[1..].join(" ");
+                Ok(Self::Today((!text.trim().is_empty()).then_some(text)))
+            }
+            Some("daemon") => match args.get(1) {
+                Some(other) => Err(eyre!("unknown argument for daemon: {other}")),
+                None => Ok(Self::Daemon),
+            },
+            Some("sync") => match args.get(1) {
+                Some(other) => Err(eyre!("unknown argument for sync: {other}")),
+                None => Ok(Self::Sync),
+            },
+            Some("undo") => match args.get(1) {
+                Some(other) => Err(eyre!("unknown argument for undo: {other}")),
+                None => Ok(Self::Undo),
+            },
+            Some("add") => {
+                let mut text = Vec::new();
+                let mut per_line = false;
+                let mut markers = Vec::new();
+                let mut at = None;
+                let mut rest = args[1..].iter();
+                while let Some(arg) = rest.next() {
+                    match arg.as_str() {
+                        "--per-line" => per_line = true,
+                        "--tag" | "--project" => {
+                            let prefix = if arg == "--tag" {
+                                tag::TAG
+                            } else {
+                                tag::PROJECT
+                            };
+                            let name = rest
+                                .next()
+                                .ok_or_else(|| eyre!("{arg} requires a name"))?
+                                .trim_start_matches(prefix);
+                            markers.push(format!("{prefix}{name}"));
+                        }
+                        "--at" => {
+                            at = Some(dates::time(
+                                rest.next().ok_or_else(|| eyre!("--at requires a time"))?,
+                                Local::now(),
+                            )?);
+                        }
+                        other if other.starts_with("--") => {
+                            return Err(eyre!("unknown argument for add: {other}"));
+                        }
+                        word => text.push(word),
+                    }
+                }
+                if per_line && !text.is_empty() {
+                    return Err(eyre!("--per-line reads entries from stdin, not arguments"));
+                }
+                Ok(Self::Add {
+                    text: text.join(" "),
+                    per_line,
+                    markers,
+                    at,
+                    print_ids: true,
+                })
+            }
+            Some(_) => Ok(Self::text(args)),
+        }
+    }
+
+    pub fn run(self, app: &mut App) -> Result<()> {
+        match self {
+            Self::Tui => {
+                let terminal = ratatui::init();
+                let result = app.run(terminal);
+                ratatui::restore();
+                result?;
+                if let Some(summary) = app.take_summary() {
+                    print!("{summary}");
+                }
+                match app.settings.backups {
+                    Some(policy) if !app.read_only() => {
+                        app.load_all()?;
+                        let data = crate::storage::encode(app, app.settings.compression)?;
+                        crate::backup::snapshot(&data, &policy)
+                    }
+                    _ => Ok(()),
+                }
+            }
+            Self::Add {
+                text,
+                per_line,
+                markers,
+                at,
+                print_ids,
+            } => {
+                let input = reads_stdin(&text, per_line)
+                    .then(|| io::read_to_string(io::stdin()))
+                    .transpose()?;
+                let mut warnings = Vec::new();
+                let result = add_entries(
+                    app,
+                    entries(text, per_line, input),
+                    &markers,
+                    at,
+                    true,
+                    &mut warnings,
+                );
+                for warning in warnings {
+                    eprintln!("{warning}");
+                }
+                if print_ids {
+                    for id in result? {
+                        println!("{id}");
+                    }
+                }
+                Ok(())
+            }
+            Self::Stats { by_hour } => {
+                app.load_all()?;
+                let distribution = HourDistribution::new(&app.logs);
+                println!("{} entries", distribution.total());
+                if by_hour {
+                    println!("{}", distribution.to_text(40));
+                }
+                Ok(())
+            }
+            Self::Serve { .. } if app.read_only() => {
+                Err(eyre!("lw serve is not available in safe mode"))
+            }
+            Self::List { with, since } => {
+                app.load_all()?;
+                print(
+                    app,
+                    app.logs.iter().filter(|item| {
+                        with.iter().all(|person| item.mentions(person))
+                            && since.is_none_or(|since| item.created().date_naive() >= since)
+                    }),
+                );
+                Ok(())
+            }
+            Self::Search(query) => {
+                app.load_all()?;
+                let matches = query.filter(&app.logs, &app.index);
+                print(app, matches.into_iter().map(|i| &app.logs[i]));
+                Ok(())
+            }
+            #[cfg(feature = "serve")]
+            Self::Serve { port } => crate::serve::run(port),
+            #[cfg(not(feature = "serve"))]
+            Self::Serve { .. } => Err(eyre!("lw was built without the serve feature")),
+            Self::Merge(path) => {
+                let report = app.merge(App::read(&path)?)?;
+                println!(
+                    "merged: {} added, {} updated, {} removed",
+                    report.added, report.updated, report.removed
+                );
+                app.save()
+            }
+            Self::Show(entry) => {
+                let item = app.find(&entry)?;
+                println!("id:       {}", item.id());
+                println!("short id: {}", app.short_id(&item));
+                println!("created:  {}", item.created().format("%Y-%m-%d %H:%M:%S"));
+                println!("modified: {}", item.modified().format("%Y-%m-%d %H:%M:%S"));
+                for (name, value) in item.fields() {
+                    println!("{:<10}{value}", format!("{name}:"));
+                }
+                for attachment in item.attachments() {
+                    println!("attached: {}", attachment.path(&item.id()).display());
+                }
+                println!("\n{}", item.content());
+                Ok(())
+            }
+            Self::Edit { entry, text } => {
+                let item = app.find(&entry)?;
+                let content = match text {
+                    Some(text) => text,
+                    None => edit_externally(&item)?,
+                };
+                if content.trim().is_empty() {
+                    return Err(eyre!("not saving an empty entry, use lw rm to delete it"));
+                }
+                if content.trim_end() == item.content().trim_end() {
+                    println!("no changes");
+                    return Ok(());
+                }
+                app.update(item.id(), content.trim_end().to_owned());
+                app.save()
+            }
+            Self::Remove(entry) => {
+                let item = app.find(&entry)?;
+                let short = app.short_id(&item);
+                app.remove(item.id());
+                println!("removed {short}: {}", app.preview(&item));
+                app.save()
+            }
+            Self::Append { entry, text } => {
+                let id = app.find(&entry)?.id();
+                app.append(&id, &text);
+                app.save()
+            }
+            Self::Set { entry, fields } => {
+                let id = app.find(&entry)?.id();
+                for (name, value) in fields {
+                    app.set_field(&id, &name, &value)?;
+                }
+                app.save()
+            }
+            Self::Attach { entry, paths, copy } => {
+                let id = app.find(&entry)?.id();
+                for path in paths {
+                    let attachment = if copy {
+                        Attachment::store(&id, &path)?
+                    } else {
+                        Attachment::link(&path)?
+                    };
+                    println!("attached {}", attachment.name());
+                    app.attach(&id, attachment);
+                }
+                app.save()
+            }
+            Self::Goals(day) => {
+                let goal = app
+                    .settings
+                    .goal
+                    .clone()
+                    .ok_or_else(|| eyre!("no goal configured, add one to the settings"))?;
+                app.load_all()?;
+                print!("{}", goal.report(&app.logs, day));
+                Ok(())
+            }
+            Self::Invoice {
+                project,
+                month,
+                rate,
+            } => {
+                app.load_all()?;
+                print!(
+                    "{}",
+                    Invoice::new(&app.logs, project.as_deref(), month, rate).to_text()
+                );
+                Ok(())
+            }
+            Self::Today(None) => {
+                app.open_today();
+                Self::Tui.run(app)
+            }
+            Self::Today(Some(text)) => {
+                app.append_today(&text);
+                app.save()
+            }
+            Self::Daemon => crate::daemon::serve(app),
+            Self::ExportVault {
+                dir,
+                period,
+                query,
+                watch,
+            } => {
+                app.load_all()?;
+                let logs = export::select(&app.logs, query.as_ref());
+                let written = vault::export(&logs, period, &dir)?;
+                println!("wrote {written} files to {}", dir.display());
+                if watch {
+                    vault::watch(app, period, query.as_ref(), &dir)?;
+                }
+                Ok(())
+            }
+            Self::ShellHook(shell) => {
+                print!("{}", shell.hook());
+                Ok(())
+            }
+            Self::Paths => {
+                paths();
+                Ok(())
+            }
+            Self::History(id) => {
+                let records = audit::read(id.as_deref())?;
+                if records.is_empty() {
+                    println!("no changes recorded");
+                }
+                for record in records {
+                    print!("{}", record.render(app.index.abbrev()));
+                }
+                Ok(())
+            }
+            Self::Compact => {
+                let before = data_size();
+                app.load_all()?;
+                app.save()?;
+                println!(
+                    "rewrote {} entries as {}: {} -> {} bytes",
+                    app.logs.len(),
+                    match app.settings.compression {
+                        Compression::None => "json",
+                        Compression::Gzip => "gzip",
+                    },
+                    before,
+                    data_size()
+                );
+                Ok(())
+            }
+            #[cfg(feature = "sync")]
+            Self::Sync => {
+                let remote = app
+                    .settings
+                    .sync
+                    .clone()
+                    .ok_or_else(|| eyre!("no sync remote configured in settings"))?;
+                let report = crate::sync::run(app, &remote)?;
+                println!(
+                    "synced: {} added, {} updated, {} removed from remote",
+                    report.added, report.updated, report.removed
+                );
+                Ok(())
+            }
+            #[cfg(not(feature = "sync"))]
+            Self::Sync => Err(eyre!("lw was built without the sync feature")),
+            #[cfg(feature = "slack")]
+            Self::PostSlack(day) => {
+                app.load_all()?;
+                let webhook = app
+                    .settings
+                    .slack_webhook
+                    .as_deref()
+                    .ok_or_else(|| eyre!("no slack_webhook configured in settings"))?;
+                crate::slack::post(webhook, &crate::slack::message(&app.logs, day))?;
+                println!("posted entries of {day} to Slack");
+                Ok(())
+            }
+            #[cfg(not(feature = "slack"))]
+            Self::PostSlack(_) => Err(eyre!("lw was built without the slack feature")),
+            Self::Export {
+                format,
+                mut period,
+                mut query,
+                mut output,
+                preset,
+            } => {
+                if let Some(name) = preset {
+                    let preset =
+                        app.settings.export_presets.get(&name).ok_or_else(|| {
+                            eyre!("no export preset named {name} in the settings")
+                        })?;
+                    if period.is_none() {
+                        period = preset.period.as_deref().map(str::parse).transpose()?;
+                    }
+                    if query.is_none() {
+                        query = preset.query.as_deref().map(str::parse).transpose()?;
+                    }
+                    output = output.or_else(|| preset.output.clone());
+                }
+                app.load_all()?;
+                let logs = export::select(&app.logs, query.as_ref());
+                let report = export::render(format, &logs, period.unwrap_or(Period::All));
+                match output {
+                    Some(path) => fs::write(&path, report)
+                        .map_err(|e| eyre!("failed to write {}: {e}", path.display())),
+                    None => {
+                        print!("{report}");
+                        Ok(())
+                    }
+                }
+            }
+            Self::Undo => {
+                match app.undo()? {
+                    Some(restored) => {
+                        for item in restored {
+                            println!("restored {}: {}", item.id(), item.content());
+                        }
+                    }
+                    None => println!("nothing to undo"),
+                }
+                app.save()
+            }
+        }
+    }
+}
+
+fn print<'a>(app: &App, items: impl Iterator<Item = &'a Item>) {
+    for item in items {
+        println!(
+            "{}  {}  {}",
+            app.short_id(item),
+            item.created().format("%Y-%m-%d %H:%M"),
+            app.preview(item)
+        );
+    }
+}
+
+/// Whether `lw add` reads its entries from stdin rather than the arguments.
+pub(crate) fn reads_stdin(text: &str, per_line: bool) -> bool {
+    per_line || text.is_empty() && !io::stdin().is_terminal()
+}
+
+/// The entries of `lw add`, one per non-empty line of `input` with `per_line`, else all
+/// of it or the text of the arguments.
+pub(crate) fn entries(text: String, per_line: bool, input: Option<String>) -> Vec<String> {
+    match input {
+        Some(input) if per_line => input
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(str::to_owned)
+            .collect(),
+        Some(input) => vec![input.trim().to_owned()],
+        None => vec![text],
+    }
+}
+
+/// Adds `entries` with `markers` and saves, returning the ids of the added ones. Notes on
+/// long entries and duplicates end up in `warnings`, a duplicate is only added again when
+/// the user may be asked and agrees.
+pub(crate) fn add_entries(
+    app: &mut App,
+    entries: Vec<String>,
+    markers: &[String],
+    at: Option<DateTime<Local>>,
+    ask: bool,
+    warnings: &mut Vec<String>,
+) -> Result<Vec<String>> {
+    if entries.iter().all(String::is_empty) {
+        return Err(eyre!("nothing to add"));
+    }
+    let mut ids = Vec::new();
+    for content in entries {
+        let content = tag::inherit(&markers.join(" "), &content);
+        let chunks = app.split_content(&content);
+        if chunks.len() == 1 && app.exceeds_max_length(&content) {
+            warnings.push("warning: entry exceeds the configured maximum length".to_owned());
+        }
+        for chunk in chunks {
+            let mut item = Item::from(chunk);
+            if let Some(at) = at {
+                item.backdate(at);
+                app.load_month(&item)?;
+            }
+            if let Some(id) = add(app, item, ask, warnings)? {
+                ids.push(id);
+            }
+        }
+    }
+    app.save()?;
+    Ok(ids)
+}
+
+/// Adds `item` unless it duplicates a recent entry, returning its id if it was added.
+fn add(app: &mut App, item: Item, ask: bool, warnings: &mut Vec<String>) -> Result<Option<String>> {
+    let id = item.id();
+    let duplicate = match app.add_unique(item) {
+        Added::New => return Ok(Some(id)),
+        Added::Skipped(duplicate) => duplicate,
+        Added::Merged(duplicate) => {
+            warnings.push(format!("merged into {}", app.short_id(&duplicate)));
+            return Ok(None);
+        }
+        Added::Confirm { duplicate, item } => {
+            if ask
+                && confirm(&format!(
+                    "the same entry was added at {}, add it again? [y/N] ",
+                    duplicate.created().format("%H:%M:%S")
+                ))?
+            {
+                app.add(item);
+                return Ok(Some(id));
+            }
+            duplicate
+        }
+    };
+    warnings.push(format!("skipped duplicate of {}", app.short_id(&duplicate)));
+    Ok(None)
+}
+
+/// Lets the user edit `item` in `$VISUAL` or `$EDITOR`, returning the new content.
+fn edit_externally(item: &Item) -> Result<String> {
+    let editor = env::var("VISUAL")
+        .or_else(|_| env::var("EDITOR"))
+        .map_err(|_| eyre!("set $EDITOR or pass the new text to lw edit"))?;
+    let mut words = editor.split_whitespace();
+    let program = words.next().ok_or_else(|| eyre!("$EDITOR is empty"))?;
+    let path = env::temp_dir().join(format!("lw-{}.txt", item.id()));
+    fs::write(&path, item.content())?;
+    let status = process::Command::new(program)
+        .args(words)
+        .arg(&path)
+        .status()
+        .map_err(|e| eyre!("failed to run {editor}: {e}"));
+    let content = fs::read_to_string(&path);
+    fs::remove_file(&path)?;
+    if !status?.success() {
+        return Err(eyre!("{editor} exited with an error, entry left unchanged"));
+    }
+    Ok(content?)
+}
+
+/// Asks a yes or no question on the terminal, answering no when stdin is not a terminal.
+fn confirm(question: &str) -> Result<bool> {
+    if !io::stdin().is_terminal() {
+        return Ok(false);
+    }
+    eprint!("{question}");
+    io::stderr().flush()?;
+    let mut answer = String::new();
+    io::stdin().lock().read_line(&mut answer)?;
+    Ok(matches!(answer.trim(), "y" | "Y" | "yes"))
+}
+
+/// Prints the directory of lw, why it is there and the files in it, whether they exist or not.
+pub fn paths() {
+    let (dir, source) = paths::config_dir();
+    let config = App::config_path();
+    println!("{} ({})", dir.display(), source.describe());
+    let files = [
+        ("data", config.clone()),
+        ("logs", config.with_file_name("logs")),
+        ("settings", Settings::path()),
+        ("attachments", config.with_file_name("attachments")),
+        ("backups", backup::dir()),
+        ("undo", Journal::path()),
+        ("draft", draft::path()),
+        ("review", review::path()),
+        ("audit", audit::path()),
+        ("popup", popup::path()),
+        ("daemon", daemon::path()),
+    ];
+    for (name, path) in files {
+        println!("  {name:<12}{}", path.display());
+    }
+}
+
+/// Size in bytes of the data file and the monthly shards.
+fn data_size() -> u64 {
+    let config = App::config_path();
+    let shards = fs::read_dir(config.with_file_name("logs"))
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| entry.metadata().ok())
+        .map(|metadata| metadata.len());
+    fs::metadata(config).map_or(0, |m| m.len()) + shards.sum::<u64>()
+}
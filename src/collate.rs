@@ -0,0 +1,26 @@
+use unicode_normalization::UnicodeNormalization;
+use unicode_normalization::char::is_combining_mark;
+
+/// Case folds `s`, including the expansions plain lowercasing misses such as `ß` to `ss`.
+pub fn fold(s: &str) -> String {
+    s.to_lowercase().replace('ß', "ss")
+}
+
+/// Key comparing strings at primary strength, i.e. ignoring case and accents, so `Ärger` sorts
+/// next to `Arbeit` instead of after `Zoo`.
+pub fn key(s: &str) -> String {
+    fold(s).nfd().filter(|c| !is_combining_mark(*c)).collect()
+}
+
+/// Orders strings alphabetically, using case and accents only to break ties.
+pub fn compare(a: &str, b: &str) -> std::cmp::Ordering {
+    key(a)
+        .cmp(&key(b))
+        .then_with(|| fold(a).cmp(&fold(b)))
+        .then_with(|| a.cmp(b))
+}
+
+/// Case and accent insensitive substring search.
+pub fn contains(haystack: &str, needle: &str) -> bool {
+    key(haystack).contains(&key(needle))
+}
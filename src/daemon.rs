@@ -0,0 +1,198 @@
+use std::path::PathBuf;
+
+#[cfg(not(unix))]
+use color_eyre::{Result, eyre::eyre};
+
+use crate::App;
+
+/// The socket the daemon listens on, next to the data file.
+pub fn path() -> PathBuf {
+    App::config_path().with_file_name("daemon.sock")
+}
+
+#[cfg(unix)]
+mod unix {
+    use std::fs;
+    use std::io::{self, BufRead, BufReader, Write};
+    use std::os::unix::net::{UnixListener, UnixStream};
+    use std::path::Path;
+    use std::thread;
+    use std::time::{Duration, SystemTime};
+
+    use color_eyre::{Result, eyre::eyre};
+    use serde::{Deserialize, Serialize};
+
+    use super::path;
+    use crate::App;
+    use crate::cli::{self, Command};
+    use crate::signal::Signals;
+
+    /// How long a client waits for the daemon before giving up.
+    static TIMEOUT: Duration = Duration::from_secs(10);
+    /// How often the daemon checks for a termination request between connections.
+    static POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+    /// An `lw add` handed to the daemon, with its arguments as given.
+    #[derive(Debug, Serialize, Deserialize)]
+    struct Request {
+        args: Vec<String>,
+        /// What was piped into `lw add`, if it reads its entries from stdin.
+        stdin: Option<String>,
+    }
+
+    /// What the daemon answers, printed by the client as if it had added the entries itself.
+    #[derive(Debug, Default, Serialize, Deserialize)]
+    struct Reply {
+        ids: Vec<String>,
+        warnings: Vec<String>,
+        error: Option<String>,
+    }
+
+    /// Runs the `lw add` of `request` without asking about duplicates.
+    fn handle(app: &mut App, request: Request) -> Reply {
+        let mut reply = Reply::default();
+        let result = match Command::parse(request.args) {
+            Ok(Command::Add {
+                text,
+                per_line,
+                markers,
+                at,
+                print_ids,
+            }) => cli::add_entries(
+                app,
+                cli::entries(text, per_line, request.stdin),
+                &markers,
+                at,
+                false,
+                &mut reply.warnings,
+            )
+            .map(|ids| {
+                if print_ids {
+                    reply.ids = ids;
+                }
+            }),
+            Ok(_) => Err(eyre!("the daemon only adds entries")),
+            Err(e) => Err(e),
+        };
+        reply.warnings.extend(
+            app.take_errors()
+                .into_iter()
+                .map(|e| format!("warning: {e}")),
+        );
+        reply.error = result.err().map(|e| e.to_string());
+        reply
+    }
+
+    /// Keeps the log loaded and adds the entries sent by `lw add` until terminated.
+    pub fn serve(app: &mut App) -> Result<()> {
+        let socket = path();
+        if socket.exists() {
+            if UnixStream::connect(&socket).is_ok() {
+                return Err(eyre!(
+                    "a daemon is already listening on {}",
+                    socket.display()
+                ));
+            }
+            // left behind by a daemon that did not exit cleanly
+            fs::remove_file(&socket)?;
+        }
+        let listener = UnixListener::bind(&socket)
+            .map_err(|e| eyre!("failed to listen on {}: {e}", socket.display()))?;
+        listener.set_nonblocking(true)?;
+        let signals = Signals::register()?;
+        println!("listening on {}", socket.display());
+        let config = App::config_path();
+        let mut loaded = modified(config);
+        let result = loop {
+            if signals.terminated() {
+                break Ok(());
+            }
+            let stream = match listener.accept() {
+                Ok((stream, _)) => stream,
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => {
+                    thread::sleep(POLL_INTERVAL);
+                    continue;
+                }
+                Err(e) => break Err(e.into()),
+            };
+            if modified(config) != loaded {
+                match App::new(config.to_owned()) {
+                    Ok(reloaded) => *app = reloaded,
+                    Err(e) => break Err(e),
+                }
+            }
+            if let Err(e) = respond(app, stream) {
+                eprintln!("warning: {e}");
+            }
+            loaded = modified(config);
+        };
+        fs::remove_file(&socket)?;
+        result
+    }
+
+    fn respond(app: &mut App, stream: UnixStream) -> Result<()> {
+        stream.set_nonblocking(false)?;
+        stream.set_read_timeout(Some(TIMEOUT))?;
+        let mut line = String::new();
+        BufReader::new(&stream).read_line(&mut line)?;
+        let reply = handle(app, serde_json::from_str(&line)?);
+        let mut stream = stream;
+        serde_json::to_writer(&mut stream, &reply)?;
+        stream.write_all(b"\n")?;
+        Ok(())
+    }
+
+    /// Hands `lw add` to a running daemon, printing its reply. Returns `false` if there is no
+    /// daemon or the arguments are not an add, to run the command in this process instead.
+    pub fn forward(args: &[String]) -> Result<bool> {
+        let Ok(Command::Add { text, per_line, .. }) = Command::parse(args.to_vec()) else {
+            return Ok(false);
+        };
+        let Ok(mut stream) = UnixStream::connect(path()) else {
+            return Ok(false);
+        };
+        let stdin = cli::reads_stdin(&text, per_line)
+            .then(|| io::read_to_string(io::stdin()))
+            .transpose()?;
+        let request = Request {
+            args: args.to_vec(),
+            stdin,
+        };
+        stream.set_read_timeout(Some(TIMEOUT))?;
+        serde_json::to_writer(&mut stream, &request)?;
+        stream.write_all(b"\n")?;
+        let mut line = String::new();
+        BufReader::new(&stream)
+            .read_line(&mut line)
+            .map_err(|e| eyre!("the daemon did not answer: {e}"))?;
+        let reply: Reply =
+            serde_json::from_str(&line).map_err(|e| eyre!("the daemon did not answer: {e}"))?;
+        for warning in reply.warnings {
+            eprintln!("{warning}");
+        }
+        if let Some(error) = reply.error {
+            return Err(eyre!(error));
+        }
+        for id in reply.ids {
+            println!("{id}");
+        }
+        Ok(true)
+    }
+
+    fn modified(path: &Path) -> Option<SystemTime> {
+        fs::metadata(path).and_then(|m| m.modified()).ok()
+    }
+}
+
+#[cfg(unix)]
+pub use unix::{forward, serve};
+
+#[cfg(not(unix))]
+pub fn serve(_app: &mut App) -> Result<()> {
+    Err(eyre!("lw daemon is only available on Unix"))
+}
+
+#[cfg(not(unix))]
+pub fn forward(_args: &[String]) -> Result<bool> {
+    Ok(false)
+}
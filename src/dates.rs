@@ -0,0 +1,172 @@
+use chrono::{
+    DateTime, Datelike, Days, Local, Months, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta,
+    Weekday,
+};
+use color_eyre::{Result, eyre::eyre};
+
+use crate::week;
+
+static DAY_HELP: &str = "expected e.g. YYYY-MM-DD, today, yesterday, monday, last friday, \
+                         last week or 2 weeks ago";
+static TIME_HELP: &str = "expected e.g. HH:MM, YYYY-MM-DD HH:MM, 4pm, yesterday 4pm, \
+                          last monday at 9:30 or 2 hours ago";
+
+/// A day written as `YYYY-MM-DD` or in words, e.g. `yesterday`, `monday` for the most recent
+/// Monday, `last friday`, `next week`, `3 days ago` or `last month` for its first day.
+pub fn day(s: &str, today: NaiveDate) -> Result<NaiveDate> {
+    let s = normalize(s);
+    parse_day(&s, today).ok_or_else(|| eyre!("invalid day {s}, {DAY_HELP}"))
+}
+
+/// A point in time written as `YYYY-MM-DD HH:MM` or in words, e.g. `4pm` for today,
+/// `yesterday 4pm`, `last monday at 9:30` or `2 hours ago`. A day alone means its midnight.
+pub fn time(s: &str, now: DateTime<Local>) -> Result<DateTime<Local>> {
+    let s = normalize(&s.replace('T', " "));
+    let time = parse_time(&s, now).ok_or_else(|| eyre!("invalid time {s}, {TIME_HELP}"))?;
+    time.and_local_timezone(Local)
+        .earliest()
+        .ok_or_else(|| eyre!("{s} does not exist in the local time zone"))
+}
+
+fn normalize(s: &str) -> String {
+    s.trim()
+        .trim_matches('"')
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+fn parse_day(s: &str, today: NaiveDate) -> Option<NaiveDate> {
+    if let Ok(day) = NaiveDate::parse_from_str(s, "%Y-%m-%d") {
+        return Some(day);
+    }
+    let words: Vec<&str> = s.split(' ').collect();
+    match words.as_slice() {
+        ["today"] | ["now"] => Some(today),
+        ["yesterday"] => today.pred_opt(),
+        ["tomorrow"] => today.succ_opt(),
+        [weekday] => {
+            let weekday = weekday.parse::<Weekday>().ok()?;
+            Some(today - Days::new(days_since(today.weekday(), weekday)))
+        }
+        ["last", "week"] => Some(week::Cursor::on(today).start - Days::new(7)),
+        ["this", "week"] => Some(week::Cursor::on(today).start),
+        ["next", "week"] => Some(week::Cursor::on(today).start + Days::new(7)),
+        ["last", "month"] => today.with_day(1)?.checked_sub_months(Months::new(1)),
+        ["this", "month"] => today.with_day(1),
+        ["next", "month"] => today.with_day(1)?.checked_add_months(Months::new(1)),
+        ["last", "year"] => NaiveDate::from_ymd_opt(today.year() - 1, 1, 1),
+        ["this", "year"] => NaiveDate::from_ymd_opt(today.year(), 1, 1),
+        ["last", weekday] => {
+            let weekday = weekday.parse::<Weekday>().ok()?;
+            let days = match days_since(today.weekday(), weekday) {
+                0 => 7,
+                days => days,
+            };
+            Some(today - Days::new(days))
+        }
+        ["next", weekday] => {
+            let weekday = weekday.parse::<Weekday>().ok()?;
+            let days = match days_since(weekday, today.weekday()) {
+                0 => 7,
+                days => days,
+            };
+            Some(today + Days::new(days))
+        }
+        [count, unit, "ago"] => {
+            let count = amount(count)?;
+            match unit.trim_end_matches('s') {
+                "day" => today.checked_sub_days(Days::new(count)),
+                "week" => today.checked_sub_days(Days::new(count.checked_mul(7)?)),
+                "month" => today.checked_sub_months(Months::new(count.try_into().ok()?)),
+                "year" => today
+                    .checked_sub_months(Months::new(u32::try_from(count).ok()?.checked_mul(12)?)),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+fn parse_time(s: &str, now: DateTime<Local>) -> Option<NaiveDateTime> {
+    let today = now.date_naive();
+    if s == "now" {
+        return Some(now.naive_local());
+    }
+    if let [count, unit, "ago"] = s.split(' ').collect::<Vec<_>>().as_slice() {
+        let count = i64::try_from(amount(count)?).ok()?;
+        let delta = match unit.trim_end_matches('s') {
+            "second" => TimeDelta::try_seconds(count),
+            "minute" | "min" => TimeDelta::try_minutes(count),
+            "hour" => TimeDelta::try_hours(count),
+            _ => None,
+        };
+        if let Some(delta) = delta {
+            return now.naive_local().checked_sub_signed(delta);
+        }
+    }
+    if let Some(time) = clock(s) {
+        return Some(today.and_time(time));
+    }
+    // a day followed by a time of day, e.g. `yesterday 4pm` or `monday at 9:30`
+    let words: Vec<&str> = s.split(' ').collect();
+    for split in [words.len().saturating_sub(1), words.len().saturating_sub(2)] {
+        if split == 0 {
+            continue;
+        }
+        if let Some(time) = clock(&words[split..].join(" ")) {
+            let day = words[..split]
+                .strip_suffix(&["at"])
+                .unwrap_or(&words[..split])
+                .join(" ");
+            return parse_day(&day, today).map(|day| day.and_time(time));
+        }
+    }
+    parse_day(s, today).map(|day| day.and_time(NaiveTime::MIN))
+}
+
+/// A time of day such as `16:30`, `16:30:15`, `4pm`, `4:30 pm`, `noon` or `midnight`.
+fn clock(s: &str) -> Option<NaiveTime> {
+    match s {
+        "noon" => return NaiveTime::from_hms_opt(12, 0, 0),
+        "midnight" => return Some(NaiveTime::MIN),
+        _ => {}
+    }
+    for format in ["%H:%M", "%H:%M:%S"] {
+        if let Ok(time) = NaiveTime::parse_from_str(s, format) {
+            return Some(time);
+        }
+    }
+    let (clock, afternoon) = match s.strip_suffix("pm") {
+        Some(clock) => (clock, true),
+        None => (s.strip_suffix("am")?, false),
+    };
+    let (hour, minute) = match clock.trim_end().split_once(':') {
+        Some((hour, minute)) => (hour.parse::<u32>().ok()?, minute.parse().ok()?),
+        None => (clock.trim_end().parse::<u32>().ok()?, 0),
+    };
+    if !(1..=12).contains(&hour) {
+        return None;
+    }
+    let hour = match (hour, afternoon) {
+        (12, false) => 0,
+        (12, true) => 12,
+        (hour, true) => hour + 12,
+        (hour, false) => hour,
+    };
+    NaiveTime::from_hms_opt(hour, minute, 0)
+}
+
+/// `3`, `a` or `an`.
+fn amount(s: &str) -> Option<u64> {
+    match s {
+        "a" | "an" | "one" => Some(1),
+        _ => s.parse().ok(),
+    }
+}
+
+/// Days to go back from `from` to reach the previous `to`, 0 if they are the same.
+fn days_since(from: Weekday, to: Weekday) -> u64 {
+    u64::from((from.num_days_from_monday() + 7 - to.num_days_from_monday()) % 7)
+}
@@ -0,0 +1,27 @@
+use std::{fs, path::PathBuf};
+
+use color_eyre::Result;
+
+use crate::log::Item;
+use crate::{App, storage};
+
+pub fn path() -> PathBuf {
+    App::config_path().with_file_name("draft.json")
+}
+
+pub fn load() -> Option<Item> {
+    let content = fs::read_to_string(path()).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+pub fn save(item: &Item) -> Result<()> {
+    storage::replace(&path(), serde_json::to_string_pretty(item)?.as_bytes())
+}
+
+pub fn discard() -> Result<()> {
+    let path = path();
+    if path.exists() {
+        fs::remove_file(path)?;
+    }
+    Ok(())
+}
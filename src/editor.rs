@@ -0,0 +1,238 @@
+use std::ops::Range;
+
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// Removes the last grapheme cluster, so accents, emoji and CJK characters are deleted as a whole.
+pub fn pop_grapheme(s: &mut String) -> bool {
+    match s.grapheme_indices(true).next_back() {
+        Some((idx, _)) => {
+            s.truncate(idx);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Returns `s` without its last word and any whitespace trailing it.
+pub fn remove_last_word(s: &str) -> &str {
+    let trimmed = s.trim_end();
+    match trimmed
+        .grapheme_indices(true)
+        .rev()
+        .find(|(_, g)| g.chars().all(char::is_whitespace))
+    {
+        Some((idx, _)) => &s[..idx],
+        None => "",
+    }
+}
+
+/// Shortens `s` to at most `width` terminal columns, marking the cut with an ellipsis.
+pub fn truncate_to_width(s: &str, width: usize) -> String {
+    if s.width() <= width {
+        return s.to_owned();
+    }
+    let limit = width.saturating_sub(1);
+    let mut used = 0;
+    let mut out = String::new();
+    for g in s.graphemes(true) {
+        let w = g.width();
+        if used + w > limit {
+            break;
+        }
+        used += w;
+        out.push_str(g);
+    }
+    if width > 0 {
+        out.push('…');
+    }
+    out
+}
+
+pub fn prev_boundary(s: &str, cursor: usize) -> usize {
+    s[..cursor]
+        .grapheme_indices(true)
+        .next_back()
+        .map_or(0, |(idx, _)| idx)
+}
+
+pub fn next_boundary(s: &str, cursor: usize) -> usize {
+    s[cursor..]
+        .graphemes(true)
+        .next()
+        .map_or(cursor, |g| cursor + g.len())
+}
+
+pub fn line_start(s: &str, cursor: usize) -> usize {
+    s[..cursor].rfind('\n').map_or(0, |idx| idx + 1)
+}
+
+pub fn line_end(s: &str, cursor: usize) -> usize {
+    s[cursor..].find('\n').map_or(s.len(), |idx| cursor + idx)
+}
+
+/// Moves to the same display column on the previous line, or to the start of the text.
+pub fn line_up(s: &str, cursor: usize) -> usize {
+    let start = line_start(s, cursor);
+    if start == 0 {
+        return 0;
+    }
+    let column = s[start..cursor].width();
+    at_column(s, line_start(s, start - 1), column)
+}
+
+/// Moves to the same display column on the next line, or to the end of the text.
+pub fn line_down(s: &str, cursor: usize) -> usize {
+    let end = line_end(s, cursor);
+    if end == s.len() {
+        return s.len();
+    }
+    let column = s[line_start(s, cursor)..cursor].width();
+    at_column(s, end + 1, column)
+}
+
+fn at_column(s: &str, start: usize, column: usize) -> usize {
+    let mut width = 0;
+    for (idx, g) in s[start..].grapheme_indices(true) {
+        if g == "\n" || width + g.width() > column {
+            return start + idx;
+        }
+        width += g.width();
+    }
+    s.len()
+}
+
+/// Start of the whitespace-delimited word that ends at `cursor`.
+pub fn word_start(s: &str, cursor: usize) -> usize {
+    s[..cursor]
+        .char_indices()
+        .rev()
+        .find(|(_, c)| c.is_whitespace())
+        .map_or(0, |(idx, c)| idx + c.len_utf8())
+}
+
+pub fn insert(s: &mut String, cursor: usize, text: &str) -> usize {
+    s.insert_str(cursor, text);
+    cursor + text.len()
+}
+
+pub fn backspace(s: &mut String, cursor: usize) -> usize {
+    let prev = prev_boundary(s, cursor);
+    s.replace_range(prev..cursor, "");
+    prev
+}
+
+pub fn delete(s: &mut String, cursor: usize) -> usize {
+    let next = next_boundary(s, cursor);
+    s.replace_range(cursor..next, "");
+    cursor
+}
+
+pub fn delete_word(s: &mut String, cursor: usize) -> usize {
+    let prev = remove_last_word(&s[..cursor]).len();
+    s.replace_range(prev..cursor, "");
+    prev
+}
+
+/// Splits `s` into the byte ranges of its visual rows, wrapping at word boundaries where possible.
+pub fn wrap(s: &str, width: usize) -> Vec<Range<usize>> {
+    let width = width.max(1);
+    let mut rows = Vec::new();
+    let mut line_start = 0;
+    for line in s.split('\n') {
+        let mut row_start = line_start;
+        let mut row_width = 0;
+        let mut last_break = None;
+        for (idx, g) in line.grapheme_indices(true) {
+            let idx = line_start + idx;
+            let w = g.width();
+            if row_width + w > width && row_width > 0 {
+                match last_break {
+                    Some(b) if b > row_start => {
+                        rows.push(row_start..b);
+                        row_width = s[b..idx].width();
+                        row_start = b;
+                    }
+                    _ => {
+                        rows.push(row_start..idx);
+                        row_start = idx;
+                        row_width = 0;
+                    }
+                }
+                last_break = None;
+            }
+            row_width += w;
+            if g.chars().all(char::is_whitespace) {
+                last_break = Some(idx + g.len());
+            }
+        }
+        rows.push(row_start..line_start + line.len());
+        line_start += line.len() + 1;
+    }
+    rows
+}
+
+/// Locates `cursor` within the rows produced by [`wrap`] as `(row, column)`.
+pub fn cursor_position(s: &str, rows: &[Range<usize>], cursor: usize) -> (usize, usize) {
+    for (i, row) in rows.iter().enumerate() {
+        let continues = rows.get(i + 1).is_some_and(|next| next.start == row.end);
+        if row.start <= cursor && (cursor < row.end || (cursor == row.end && !continues)) {
+            return (i, s[row.start..cursor].width());
+        }
+    }
+    (0, 0)
+}
+
+/// Returns the first line containing more than whitespace and whether anything follows it.
+pub fn first_line(s: &str) -> (&str, bool) {
+    let mut lines = s.lines().map(str::trim).filter(|l| !l.is_empty());
+    let first = lines.next().unwrap_or_default();
+    (first, lines.next().is_some())
+}
+
+/// Shortens `s` to at most `max` characters, cutting at the last word boundary that fits.
+pub fn summarize(s: &str, max: usize) -> String {
+    if len(s) <= max {
+        return s.to_owned();
+    }
+    let end = s
+        .grapheme_indices(true)
+        .nth(max.saturating_sub(1))
+        .map_or(s.len(), |(idx, _)| idx);
+    let head = &s[..end];
+    let cut = head
+        .rfind(char::is_whitespace)
+        .filter(|idx| *idx > 0)
+        .unwrap_or(end);
+    format!("{}…", head[..cut].trim_end())
+}
+
+pub fn len(s: &str) -> usize {
+    s.graphemes(true).count()
+}
+
+/// Breaks `s` into chunks of at most `max` characters, preferring line breaks over other
+/// whitespace and cutting words only when nothing else fits.
+pub fn split_to_length(s: &str, max: usize) -> Vec<String> {
+    let max = max.max(1);
+    let mut chunks = Vec::new();
+    let mut rest = s.trim();
+    while len(rest) > max {
+        let end = rest
+            .grapheme_indices(true)
+            .nth(max)
+            .map_or(rest.len(), |(idx, _)| idx);
+        let head = &rest[..end];
+        let cut = head
+            .rfind('\n')
+            .or_else(|| head.rfind(char::is_whitespace))
+            .filter(|idx| *idx > 0)
+            .unwrap_or(end);
+        chunks.push(rest[..cut].trim_end().to_owned());
+        rest = rest[cut..].trim_start();
+    }
+    if !rest.is_empty() {
+        chunks.push(rest.to_owned());
+    }
+    chunks
+}
@@ -0,0 +1,210 @@
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::str::FromStr;
+
+use chrono::{Datelike, Local, NaiveDate};
+use color_eyre::{Report, Result};
+
+use crate::log::Item;
+use crate::query::Query;
+use crate::{dates, week};
+
+static NO_PROJECT: &str = "No project";
+
+static STYLE: &str = "\
+body { font-family: system-ui, sans-serif; max-width: 48rem; margin: 2rem auto; color: #222; }
+h1 { color: #0b4f6c; border-bottom: 2px solid #0b4f6c; padding-bottom: 0.25rem; }
+h2 { color: #01baef; margin-top: 2rem; }
+h3 { color: #555; font-size: 1rem; margin-bottom: 0.25rem; }
+ul { margin-top: 0; }
+li { margin: 0.2rem 0; }
+time { color: #888; font-variant-numeric: tabular-nums; margin-right: 0.5rem; }
+table { border-collapse: collapse; margin-top: 1rem; }
+th, td { text-align: left; padding: 0.25rem 1rem 0.25rem 0; }
+th { border-bottom: 1px solid #ccc; }
+.total { font-weight: bold; border-top: 1px solid #ccc; }
+.field { color: #888; font-size: 0.85rem; margin-left: 0.5rem; }
+";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Html,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Period {
+    Day,
+    Week,
+    Month,
+    /// From the day on, e.g. `--since "last monday"`.
+    Since(NaiveDate),
+    All,
+}
+
+impl Period {
+    /// The first day covered by this period, or `None` if it is unbounded.
+    pub fn start(self, today: NaiveDate) -> Option<NaiveDate> {
+        match self {
+            Self::Day => Some(today),
+            Self::Week => Some(week::Cursor::on(today).start),
+            Self::Month => today.with_day(1),
+            Self::Since(day) => Some(day),
+            Self::All => None,
+        }
+    }
+
+    fn title(self) -> &'static str {
+        match self {
+            Self::Day => "Daily report",
+            Self::Week => "Weekly report",
+            Self::Month => "Monthly report",
+            Self::Since(_) => "Report",
+            Self::All => "Report",
+        }
+    }
+}
+
+/// `day`, `week`, `month`, `all` or the day to start from, e.g. `2026-01-01` or `last monday`.
+impl FromStr for Period {
+    type Err = Report;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(match s {
+            "day" => Self::Day,
+            "week" => Self::Week,
+            "month" => Self::Month,
+            "all" => Self::All,
+            day => Self::Since(dates::day(day, Local::now().date_naive())?),
+        })
+    }
+}
+
+/// The entries of `logs` matching `query`, all of them without one.
+pub fn select<'a>(logs: &'a [Item], query: Option<&Query>) -> Cow<'a, [Item]> {
+    match query {
+        Some(query) => logs
+            .iter()
+            .filter(|item| query.matches(item))
+            .cloned()
+            .collect(),
+        None => Cow::Borrowed(logs),
+    }
+}
+
+pub fn render(format: Format, logs: &[Item], period: Period) -> String {
+    match format {
+        Format::Html => html(logs, period),
+    }
+}
+
+/// Renders a self-contained HTML page listing the entries of `period`,
+/// grouped by day and project, with entry counts per project.
+pub fn html(logs: &[Item], period: Period) -> String {
+    let start = period.start(Local::now().date_naive());
+    let mut days: BTreeMap<NaiveDate, BTreeMap<String, Vec<&Item>>> = BTreeMap::new();
+    let mut totals: BTreeMap<String, usize> = BTreeMap::new();
+    let mut entries = 0;
+    for item in logs {
+        let day = item.created().date_naive();
+        if start.is_some_and(|start| day < start) {
+            continue;
+        }
+        entries += 1;
+        let mut projects = item.projects();
+        if projects.is_empty() {
+            projects.push(NO_PROJECT.to_owned());
+        }
+        for project in projects {
+            *totals.entry(project.clone()).or_default() += 1;
+            days.entry(day)
+                .or_default()
+                .entry(project)
+                .or_default()
+                .push(item);
+        }
+    }
+
+    let title = match (start, days.keys().next_back()) {
+        (Some(start), Some(end)) if start != *end => {
+            format!("{} {start} – {end}", period.title())
+        }
+        (Some(start), _) => format!("{} {start}", period.title()),
+        (None, _) => period.title().to_owned(),
+    };
+
+    let mut page = String::new();
+    let _ = write!(
+        page,
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>{title}</title>\n<style>\n{STYLE}</style>\n</head>\n<body>\n<h1>{title}</h1>\n"
+    );
+    if days.is_empty() {
+        page.push_str("<p>No entries.</p>\n");
+    }
+    for (day, projects) in &days {
+        let _ = writeln!(page, "<h2>{}</h2>", day.format("%A, %Y-%m-%d"));
+        for (project, items) in projects {
+            let _ = writeln!(page, "<h3>{} ({})</h3>\n<ul>", escape(project), items.len());
+            let mut items = items.clone();
+            items.sort_by_key(|item| item.created());
+            for item in items {
+                let mut fields: String = item
+                    .fields()
+                    .iter()
+                    .map(|(name, value)| {
+                        format!(
+                            " <span class=\"field\">{}: {}</span>",
+                            escape(name),
+                            escape(value)
+                        )
+                    })
+                    .collect();
+                if item.billable() {
+                    fields.push_str(" <span class=\"field\">billable</span>");
+                }
+                if item.done() {
+                    fields.push_str(" <span class=\"field\">done</span>");
+                }
+                let _ = writeln!(
+                    page,
+                    "<li><time>{}</time>{}{fields}</li>",
+                    item.created().format("%H:%M"),
+                    escape(&item.content()).replace('\n', "<br>")
+                );
+            }
+            page.push_str("</ul>\n");
+        }
+    }
+    if !totals.is_empty() {
+        page.push_str("<h2>Totals</h2>\n<table>\n<tr><th>Project</th><th>Entries</th></tr>\n");
+        for (project, count) in &totals {
+            let _ = writeln!(
+                page,
+                "<tr><td>{}</td><td>{count}</td></tr>",
+                escape(project)
+            );
+        }
+        let _ = writeln!(
+            page,
+            "<tr class=\"total\"><td>Total</td><td>{entries}</td></tr>\n</table>"
+        );
+    }
+    page.push_str("</body>\n</html>\n");
+    page
+}
+
+fn escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,33 @@
+use chrono::{DateTime, Local, TimeDelta};
+use serde::{Deserialize, Serialize};
+
+/// The entry being worked on, shown in a banner until it is stopped.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Current {
+    pub id: String,
+    pub since: DateTime<Local>,
+}
+
+impl Current {
+    pub fn new(id: String) -> Self {
+        Self {
+            id,
+            since: Local::now(),
+        }
+    }
+
+    pub fn elapsed(&self) -> TimeDelta {
+        Local::now() - self.since
+    }
+}
+
+/// Formats `duration` as `H:MM:SS`.
+pub fn format(duration: TimeDelta) -> String {
+    let seconds = duration.num_seconds().max(0);
+    format!(
+        "{}:{:02}:{:02}",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
@@ -0,0 +1,126 @@
+use std::fmt::Write;
+
+use chrono::{Datelike, Days, Local, NaiveDate, TimeDelta, Weekday};
+use serde::{Deserialize, Serialize};
+
+use crate::log::Item;
+use crate::week;
+
+/// Minimum amount of logging per day, e.g. 6 hours or 3 entries on every weekday. A day meets
+/// the goal as soon as one of its targets is reached.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Goal {
+    /// Time tracked on the entries created that day.
+    pub hours: Option<f64>,
+    pub entries: Option<usize>,
+    /// Days the goal applies to, Monday to Friday unless configured.
+    pub days: Vec<Weekday>,
+}
+
+impl Default for Goal {
+    fn default() -> Self {
+        Self {
+            hours: None,
+            entries: None,
+            days: vec![
+                Weekday::Mon,
+                Weekday::Tue,
+                Weekday::Wed,
+                Weekday::Thu,
+                Weekday::Fri,
+            ],
+        }
+    }
+}
+
+/// What was logged on a day.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Progress {
+    pub entries: usize,
+    pub tracked: TimeDelta,
+}
+
+impl Progress {
+    pub fn on(logs: &[Item], day: NaiveDate) -> Self {
+        logs.iter()
+            .filter(|item| item.created().date_naive() == day)
+            .fold(Self::default(), |progress, item| Self {
+                entries: progress.entries + 1,
+                tracked: progress.tracked + item.tracked(),
+            })
+    }
+
+    fn hours(&self) -> f64 {
+        self.tracked.num_seconds() as f64 / 3600.0
+    }
+}
+
+impl Goal {
+    pub fn applies(&self, day: NaiveDate) -> bool {
+        self.days.contains(&day.weekday()) && (self.hours.is_some() || self.entries.is_some())
+    }
+
+    pub fn met(&self, progress: &Progress) -> bool {
+        self.entries
+            .is_some_and(|entries| progress.entries >= entries)
+            || self.hours.is_some_and(|hours| progress.hours() >= hours)
+    }
+
+    /// Whether `day` is over and fell short of the goal.
+    pub fn missed(&self, logs: &[Item], day: NaiveDate) -> bool {
+        self.applies(day) && day < Local::now().date_naive() && !self.met(&Progress::on(logs, day))
+    }
+
+    /// Progress towards the targets, e.g. `2/3 entries · 1.5/6h`.
+    pub fn describe(&self, progress: &Progress) -> String {
+        let mut parts = Vec::new();
+        if let Some(entries) = self.entries {
+            parts.push(format!("{}/{entries} entries", progress.entries));
+        }
+        if let Some(hours) = self.hours {
+            parts.push(format!("{:.1}/{hours}h", progress.hours()));
+        }
+        parts.join(" · ")
+    }
+
+    /// Every day of the week starting at `start`, marked as met, missed or not counted.
+    pub fn report(&self, logs: &[Item], start: NaiveDate) -> String {
+        let cursor = week::Cursor::on(start);
+        let today = Local::now().date_naive();
+        let mut text = String::new();
+        let _ = writeln!(
+            text,
+            "Week {} · {} – {}",
+            cursor.number(),
+            cursor.start.format("%b %d"),
+            cursor.end().format("%b %d, %Y")
+        );
+        let (mut met, mut counted) = (0, 0);
+        for offset in 0..7 {
+            let day = cursor.start + Days::new(offset);
+            let progress = Progress::on(logs, day);
+            let mark = match (self.applies(day), self.met(&progress)) {
+                (false, _) => "·",
+                (true, true) => {
+                    counted += 1;
+                    met += 1;
+                    "✓"
+                }
+                (true, false) if day >= today => "…",
+                (true, false) => {
+                    counted += 1;
+                    "✗"
+                }
+            };
+            let _ = writeln!(
+                text,
+                "{}  {mark}  {}",
+                day.format("%a %Y-%m-%d"),
+                self.describe(&progress)
+            );
+        }
+        let _ = writeln!(text, "Goal met on {met} of {counted} days");
+        text
+    }
+}
@@ -0,0 +1,149 @@
+use std::io::{self, Read, Write};
+use std::process::{self, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use color_eyre::{Result, eyre::eyre};
+use serde::{Deserialize, Serialize};
+
+use crate::log::Item;
+
+/// How long a hook may run before it is stopped, since the save waits for it.
+static TIMEOUT: Duration = Duration::from_secs(10);
+static POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// External commands run after a save for the changes it wrote, e.g. `{ "on_add":
+/// "notify-send lw \"$(jq -r .content)\"" }`. They are run by the shell and get the entry as
+/// JSON on stdin, `on_save` gets every change of the save. The save waits for them, each for
+/// at most [`TIMEOUT`].
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Hooks {
+    pub on_add: Option<String>,
+    /// Also run when an entry is pinned, marked as billable or gets a field or an attachment.
+    pub on_update: Option<String>,
+    pub on_delete: Option<String>,
+    pub on_save: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Event {
+    Add,
+    Update,
+    Delete,
+}
+
+/// A change waiting for the next save, the entry as it was after it.
+#[derive(Debug, Clone, Serialize)]
+pub struct Change {
+    pub event: Event,
+    pub item: Item,
+}
+
+impl Hooks {
+    fn command(&self, event: Event) -> Option<&str> {
+        match event {
+            Event::Add => self.on_add.as_deref(),
+            Event::Update => self.on_update.as_deref(),
+            Event::Delete => self.on_delete.as_deref(),
+        }
+    }
+
+    /// Whether a change of this kind has to be remembered until the next save.
+    pub fn wants(&self, event: Event) -> bool {
+        self.command(event).is_some() || self.on_save.is_some()
+    }
+
+    /// Runs the hook of each change, then `on_save` with all of them. A failing hook does not
+    /// stop the others, the failures are returned.
+    pub fn run(&self, changes: &[Change]) -> Vec<String> {
+        let mut failures = Vec::new();
+        for change in changes {
+            if let Some(command) = self.command(change.event)
+                && let Err(e) = serde_json::to_vec(&change.item)
+                    .map_err(Into::into)
+                    .and_then(|input| run(command, change.event, input))
+            {
+                failures.push(e.to_string());
+            }
+        }
+        if let Some(ref command) = self.on_save
+            && let Err(e) = serde_json::to_vec(changes)
+                .map_err(Into::into)
+                .and_then(|input| run(command, "save", input))
+        {
+            failures.push(e.to_string());
+        }
+        failures
+    }
+}
+
+/// Runs `command` with `input` on stdin and `LW_EVENT` set to `event`, waiting for it to exit
+/// or stopping it after [`TIMEOUT`].
+fn run(command: &str, event: impl Serialize, input: Vec<u8>) -> Result<()> {
+    let mut shell = if cfg!(windows) {
+        let mut shell = process::Command::new("cmd");
+        shell.arg("/C");
+        shell
+    } else {
+        let mut shell = process::Command::new("sh");
+        shell.arg("-c");
+        shell
+    };
+    let event = serde_json::to_value(event)?;
+    let mut child = shell
+        .arg(command)
+        .env("LW_EVENT", event.as_str().unwrap_or_default())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| eyre!("failed to run hook {command}: {e}"))?;
+    // read while it runs, so a hook writing a lot to stderr does not block on the pipe
+    let stderr = child.stderr.take().map(|mut pipe| {
+        thread::spawn(move || {
+            let mut stderr = Vec::new();
+            let _ = pipe.read_to_end(&mut stderr);
+            stderr
+        })
+    });
+    // written aside as well, so a hook that does not read it is still stopped in time
+    let stdin = child
+        .stdin
+        .take()
+        .map(|mut pipe| thread::spawn(move || pipe.write_all(&input)));
+    let deadline = Instant::now() + TIMEOUT;
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if Instant::now() < deadline => thread::sleep(POLL_INTERVAL),
+            Ok(None) => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(eyre!(
+                    "hook {command} was stopped after {}s",
+                    TIMEOUT.as_secs()
+                ));
+            }
+            Err(e) => return Err(eyre!("failed to run hook {command}: {e}")),
+        }
+    };
+    // hooks that do not care about the entry may exit without reading it
+    if let Some(Ok(Err(e))) = stdin.map(thread::JoinHandle::join)
+        && e.kind() != io::ErrorKind::BrokenPipe
+    {
+        return Err(eyre!("failed to run hook {command}: {e}"));
+    }
+    if status.success() {
+        return Ok(());
+    }
+    let stderr = stderr
+        .and_then(|reader| reader.join().ok())
+        .unwrap_or_default();
+    let stderr = String::from_utf8_lossy(&stderr);
+    match stderr.lines().last() {
+        Some(line) => Err(eyre!("hook {command} failed: {line}")),
+        None => Err(eyre!("hook {command} failed with {status}")),
+    }
+}
@@ -0,0 +1,625 @@
+use std::env;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use chrono::{Datelike, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+
+/// Language of the TUI. The output of the CLI stays in English so scripts can rely on it.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Language {
+    #[default]
+    En,
+    De,
+    Fr,
+}
+
+static LANGUAGE: AtomicU8 = AtomicU8::new(0);
+
+impl Language {
+    /// The language of the locale, English unless it is one with a translation.
+    pub fn detect() -> Self {
+        match locale().as_deref().and_then(|locale| locale.get(..2)) {
+            Some("de") => Self::De,
+            Some("fr") => Self::Fr,
+            _ => Self::En,
+        }
+    }
+
+    pub fn current() -> Self {
+        match LANGUAGE.load(Ordering::Relaxed) {
+            1 => Self::De,
+            2 => Self::Fr,
+            _ => Self::En,
+        }
+    }
+
+    /// Makes this the language [`t`] translates into.
+    pub fn set(self) {
+        LANGUAGE.store(self as u8, Ordering::Relaxed);
+    }
+}
+
+/// The locale of the environment, e.g. `de_DE.UTF-8`, by the precedence of `LC_ALL`,
+/// `LC_MESSAGES` and `LANG`.
+pub fn locale() -> Option<String> {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .into_iter()
+        .filter_map(|name| env::var(name).ok())
+        .find(|value| !value.is_empty() && value != "C" && value != "POSIX")
+}
+
+/// The country of the locale, e.g. `US` for `en_US.UTF-8`.
+pub fn region() -> Option<String> {
+    let locale = locale()?;
+    let (_, region) = locale.split_once('_')?;
+    Some(
+        region
+            .chars()
+            .take_while(char::is_ascii_alphabetic)
+            .collect(),
+    )
+}
+
+/// `text` in the current language, unchanged if there is no translation for it.
+pub fn t(text: &str) -> &str {
+    let catalog = match Language::current() {
+        Language::En => return text,
+        Language::De => DE,
+        Language::Fr => FR,
+    };
+    catalog
+        .iter()
+        .find(|(english, _)| *english == text)
+        .map_or(text, |(_, translation)| translation)
+}
+
+/// Translates `text` and replaces its `{}` placeholders with `values` in order.
+pub fn fill(text: &str, values: &[&str]) -> String {
+    values.iter().fold(t(text).to_owned(), |text, value| {
+        text.replacen("{}", value, 1)
+    })
+}
+
+/// Abbreviated name of `day`, e.g. `Mon`, `Mo` or `lun`.
+pub fn weekday(day: Weekday) -> &'static str {
+    let names = match Language::current() {
+        Language::En => ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"],
+        Language::De => ["Mo", "Di", "Mi", "Do", "Fr", "Sa", "So"],
+        Language::Fr => ["lun", "mar", "mer", "jeu", "ven", "sam", "dim"],
+    };
+    names[day.num_days_from_monday() as usize]
+}
+
+/// Day and abbreviated month in the order of the language, e.g. `Oct 14` or `14. Okt.`.
+pub fn short_date(date: NaiveDate) -> String {
+    let month = date.month0() as usize;
+    match Language::current() {
+        Language::En => format!("{} {:02}", EN_MONTHS[month], date.day()),
+        Language::De => format!("{}. {}", date.day(), DE_MONTHS[month]),
+        Language::Fr => format!("{} {}", date.day(), FR_MONTHS[month]),
+    }
+}
+
+/// Like [`short_date`] with the year, e.g. `Oct 14, 2026` or `14. Okt. 2026`.
+pub fn long_date(date: NaiveDate) -> String {
+    match Language::current() {
+        Language::En => format!("{}, {}", short_date(date), date.year()),
+        Language::De | Language::Fr => format!("{} {}", short_date(date), date.year()),
+    }
+}
+
+/// Abbreviated month with the year, e.g. `Oct 2026` or `Okt. 2026`.
+pub fn month(date: NaiveDate) -> String {
+    let month = date.month0() as usize;
+    let name = match Language::current() {
+        Language::En => EN_MONTHS[month],
+        Language::De => DE_MONTHS[month],
+        Language::Fr => FR_MONTHS[month],
+    };
+    format!("{name} {}", date.year())
+}
+
+static EN_MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+static DE_MONTHS: [&str; 12] = [
+    "Jan.", "Feb.", "März", "Apr.", "Mai", "Juni", "Juli", "Aug.", "Sep.", "Okt.", "Nov.", "Dez.",
+];
+static FR_MONTHS: [&str; 12] = [
+    "janv.", "févr.", "mars", "avr.", "mai", "juin", "juil.", "août", "sept.", "oct.", "nov.",
+    "déc.",
+];
+
+static DE: &[(&str, &str)] = &[
+    // main view
+    ("Log Your Work", "Protokolliere deine Arbeit"),
+    (
+        "Welcome to lw, your personal work log!
+Press <o> for a new entry or <i> to quickly add one, <e> to open the selected entry and <CTRL-p> to find every command. <CTRL-Enter> saves the entry you are editing.
+Mention #tags, +projects and @people to find entries later with </>.
+Delete this entry with <d> <d> once you are done. #lw",
+        "Willkommen bei lw, deinem persönlichen Arbeitsprotokoll!
+Drücke <o> für einen neuen Eintrag oder <i>, um schnell einen hinzuzufügen, <e>, um den ausgewählten Eintrag zu öffnen, und <CTRL-p> für alle Befehle. <CTRL-Enter> speichert den Eintrag, den du bearbeitest.
+Erwähne #tags, +projekte und @personen, um Einträge später mit </> zu finden.
+Lösche diesen Eintrag mit <d> <d>, wenn du fertig bist. #lw",
+    ),
+    ("today", "heute"),
+    ("unsaved", "ungespeichert"),
+    (
+        "safe mode: read-only",
+        "abgesicherter Modus: schreibgeschützt",
+    ),
+    ("copied {}", "{} kopiert"),
+    ("Saved", "Gespeichert"),
+    ("Saved as {} entries", "Als {} Einträge gespeichert"),
+    ("No suggestions", "Keine Vorschläge"),
+    ("No misspelled word at the cursor", "Kein falsch geschriebenes Wort am Cursor"),
+    ("Added", "Hinzugefügt"),
+    (
+        "Skipped, the same entry was added just before",
+        "Übersprungen, derselbe Eintrag wurde gerade erst hinzugefügt",
+    ),
+    (
+        "Merged into the same entry added just before",
+        "Mit demselben, gerade erst hinzugefügten Eintrag zusammengeführt",
+    ),
+    ("Undone", "Rückgängig gemacht"),
+    ("Nothing to undo", "Nichts rückgängig zu machen"),
+    (
+        "Deleted {}, <u> to undo",
+        "{} gelöscht, <u> macht es rückgängig",
+    ),
+    ("Pinned", "Angeheftet"),
+    ("Unpinned", "Gelöst"),
+    ("Marked as billable", "Als abrechenbar markiert"),
+    ("Marked as not billable", "Als nicht abrechenbar markiert"),
+    ("Started working on the entry", "Arbeit am Eintrag begonnen"),
+    ("Stopped working on the entry", "Arbeit am Eintrag beendet"),
+    ("New", "Neu"),
+    ("Quick Add", "Schnell hinzufügen"),
+    ("Select", "Auswählen"),
+    ("Pin", "Anheften"),
+    ("Undo", "Rückgängig"),
+    ("Stats", "Statistik"),
+    ("Quit", "Beenden"),
+    ("Log", "Eintrag"),
+    ("Modified", "Geändert"),
+    ("Created", "Erstellt"),
+    ("No entries match {}", "Keine Einträge für {}"),
+    ("Nothing here yet", "Noch nichts hier"),
+    ("Working on", "Arbeite an"),
+    ("Add", "Hinzufügen"),
+    ("Cancel", "Abbrechen"),
+    ("Search", "Suche"),
+    ("Apply", "Anwenden"),
+    ("Close", "Schließen"),
+    ("item {}/{}", "Eintrag {}/{}"),
+    // actions
+    ("New entry", "Neuer Eintrag"),
+    ("Quick add", "Schnell hinzufügen"),
+    ("Open today's note", "Heutige Notiz öffnen"),
+    ("Open selected entry", "Ausgewählten Eintrag öffnen"),
+    ("Delete selected entry", "Ausgewählten Eintrag löschen"),
+    (
+        "Pin or unpin selected entry",
+        "Ausgewählten Eintrag anheften oder lösen",
+    ),
+    (
+        "Mark selected entry as billable or not",
+        "Ausgewählten Eintrag als abrechenbar markieren oder nicht",
+    ),
+    (
+        "Copy the short id of selected entry",
+        "Kurze Id des ausgewählten Eintrags kopieren",
+    ),
+    (
+        "Copy the full id of selected entry",
+        "Vollständige Id des ausgewählten Eintrags kopieren",
+    ),
+    (
+        "Start or stop working on selected entry",
+        "Arbeit am ausgewählten Eintrag beginnen oder beenden",
+    ),
+    (
+        "Follow up on selected entry",
+        "Folgeeintrag zum ausgewählten Eintrag",
+    ),
+    (
+        "Undo last deletion or edit",
+        "Letzte Löschung oder Änderung rückgängig machen",
+    ),
+    ("Save all changes", "Alle Änderungen speichern"),
+    (
+        "The terminal is too small, lw needs at least {} columns and {} rows.",
+        "Das Terminal ist zu klein, lw braucht mindestens {} Spalten und {} Zeilen.",
+    ),
+    ("Down", "Runter"),
+    ("Up", "Hoch"),
+    ("Top", "Anfang"),
+    ("Bottom", "Ende"),
+    ("Cycle row density", "Zeilendichte wechseln"),
+    (
+        "Toggle sorting by date or alphabetically",
+        "Nach Datum oder alphabetisch sortieren",
+    ),
+    (
+        "Show a month, week, day or everything",
+        "Einen Monat, eine Woche, einen Tag oder alles zeigen",
+    ),
+    ("Show the previous period", "Den vorherigen Zeitraum zeigen"),
+    ("Show the next period", "Den nächsten Zeitraum zeigen"),
+    (
+        "Search, e.g. tag:foo project:acme /deploy.*prod/ before:2024-06-01",
+        "Suchen, z. B. tag:foo project:acme /deploy.*prod/ before:2024-06-01",
+    ),
+    ("Filter by person", "Nach Person filtern"),
+    ("Clear the search", "Suche zurücksetzen"),
+    ("Statistics", "Statistik"),
+    ("Week view", "Wochenansicht"),
+    (
+        "Switch to the previous view",
+        "Zur vorherigen Ansicht wechseln",
+    ),
+    ("Export this week as HTML", "Diese Woche als HTML exportieren"),
+    ("Review today's entries", "Die heutigen Einträge durchgehen"),
+    ("Command palette", "Befehlspalette"),
+    // command palette
+    ("Commands", "Befehle"),
+    ("Run", "Ausführen"),
+    ("No matching command", "Kein passender Befehl"),
+    ("Exported the week to {}", "Woche nach {} exportiert"),
+    // review
+    ("Review", "Rückblick"),
+    ("Nothing was logged today", "Heute wurde nichts erfasst"),
+    ("Next", "Weiter"),
+    ("Previous", "Zurück"),
+    ("Edit", "Bearbeiten"),
+    ("Done", "Erledigt"),
+    ("Time", "Zeit"),
+    ("Finish", "Abschließen"),
+    ("Add time, e.g. 1h30m:", "Zeit hinzufügen, z. B. 1h30m:"),
+    ("Not a duration: {}", "Keine Dauer: {}"),
+    ("Copied the summary of the day", "Zusammenfassung des Tages kopiert"),
+    ("Tracked: {}", "Erfasst: {}"),
+    // editor
+    ("Details", "Details"),
+    ("characters", "Zeichen"),
+    ("Save", "Speichern"),
+    ("Split", "Aufteilen"),
+    ("Timestamp", "Zeitstempel"),
+    ("Links", "Verknüpfungen"),
+    ("Fields", "Felder"),
+    ("Linked entries", "Verknüpfte Einträge"),
+    ("follow-up of", "Folgeeintrag von"),
+    ("followed up by", "fortgesetzt in"),
+    ("Attachments", "Anhänge"),
+    ("yes", "ja"),
+    ("no", "nein"),
+    ("invalid value for {}: {}", "ungültiger Wert für {}: {}"),
+    ("{} · modified at {}", "{} · geändert am {}"),
+    ("created at {}", "erstellt am {}"),
+    (
+        "created at {}, worked on for {}",
+        "erstellt am {}, daran gearbeitet {}",
+    ),
+    // dialogs
+    ("Unsaved Draft", "Ungespeicherter Entwurf"),
+    ("Restore", "Wiederherstellen"),
+    ("Discard", "Verwerfen"),
+    (
+        "An edit from {} was not saved. Restore it?",
+        "Eine Änderung von {} wurde nicht gespeichert. Wiederherstellen?",
+    ),
+    ("What's new in {}", "Neu in {}"),
+    ("Scroll", "Blättern"),
+    (
+        "Your data file was updated",
+        "Deine Datendatei wurde aktualisiert",
+    ),
+    ("Unsaved Changes", "Ungespeicherte Änderungen"),
+    ("Save and quit", "Speichern und beenden"),
+    (
+        "The log has changes that were not saved. Save them before quitting?",
+        "Das Protokoll hat ungespeicherte Änderungen. Vor dem Beenden speichern?",
+    ),
+    ("Quit lw?", "lw beenden?"),
+    (
+        "Unsaved changes, press <ESC> again or <q> to quit",
+        "Ungespeicherte Änderungen, zum Beenden erneut <ESC> oder <q> drücken",
+    ),
+    ("Duplicate Entry", "Doppelter Eintrag"),
+    ("Add anyway", "Trotzdem hinzufügen"),
+    (
+        "The same entry was added at {}. Add it again?",
+        "Derselbe Eintrag wurde um {} hinzugefügt. Erneut hinzufügen?",
+    ),
+    ("Saving Failed", "Speichern fehlgeschlagen"),
+    ("Retry", "Erneut versuchen"),
+    ("Later", "Später"),
+    (
+        "The changes are kept until they can be saved.",
+        "Die Änderungen bleiben erhalten, bis sie gespeichert werden können.",
+    ),
+    (
+        "The log could not be loaded",
+        "Das Protokoll konnte nicht geladen werden",
+    ),
+    ("Set aside", "Beiseitelegen"),
+    (
+        "{} is damaged. Set it aside to start without it, it is kept next to the original for recovery.",
+        "{} ist beschädigt. Lege die Datei beiseite, um ohne sie zu starten. Sie bleibt zur Wiederherstellung neben dem Original erhalten.",
+    ),
+    (
+        "Fix the problem and retry, your data has not been changed.",
+        "Behebe das Problem und versuche es erneut, deine Daten wurden nicht verändert.",
+    ),
+    (
+        "The damaged file was moved to {}.",
+        "Die beschädigte Datei wurde nach {} verschoben.",
+    ),
+    // week view and statistics
+    ("Week {} · {} – {}", "Woche {} · {} – {}"),
+    ("Move", "Bewegen"),
+    ("Open", "Öffnen"),
+    ("Week", "Woche"),
+    ("Today", "Heute"),
+    ("Go to", "Gehe zu"),
+    ("Go", "Los"),
+    ("Back", "Zurück"),
+    ("Time of day", "Tageszeit"),
+    (
+        "{} entries, busiest hour {}",
+        "{} Einträge, aktivste Stunde {}",
+    ),
+    ("Sort", "Sortieren"),
+    ("Projects, last {} days", "Projekte, letzte {} Tage"),
+    ("by tracked time", "nach erfasster Zeit"),
+    ("by entries", "nach Einträgen"),
+    ("{} entries, {} tracked", "{} Einträge, {} erfasst"),
+];
+
+static FR: &[(&str, &str)] = &[
+    // main view
+    ("Log Your Work", "Journal de travail"),
+    (
+        "Welcome to lw, your personal work log!
+Press <o> for a new entry or <i> to quickly add one, <e> to open the selected entry and <CTRL-p> to find every command. <CTRL-Enter> saves the entry you are editing.
+Mention #tags, +projects and @people to find entries later with </>.
+Delete this entry with <d> <d> once you are done. #lw",
+        "Bienvenue dans lw, votre journal de travail personnel !
+Appuyez sur <o> pour une nouvelle entrée ou sur <i> pour en ajouter une rapidement, sur <e> pour ouvrir l'entrée sélectionnée et sur <CTRL-p> pour trouver toutes les commandes. <CTRL-Enter> enregistre l'entrée en cours de modification.
+Mentionnez des #tags, des +projets et des @personnes pour retrouver vos entrées avec </>.
+Supprimez cette entrée avec <d> <d> quand vous avez terminé. #lw",
+    ),
+    ("today", "aujourd'hui"),
+    ("unsaved", "non enregistré"),
+    ("safe mode: read-only", "mode sans échec : lecture seule"),
+    ("copied {}", "{} copié"),
+    ("Saved", "Enregistré"),
+    ("Saved as {} entries", "Enregistré en {} entrées"),
+    ("No suggestions", "Aucune suggestion"),
+    ("No misspelled word at the cursor", "Aucun mot mal orthographié au curseur"),
+    ("Added", "Ajoutée"),
+    (
+        "Skipped, the same entry was added just before",
+        "Ignorée, la même entrée vient d'être ajoutée",
+    ),
+    (
+        "Merged into the same entry added just before",
+        "Fusionnée avec la même entrée ajoutée juste avant",
+    ),
+    ("Undone", "Annulé"),
+    ("Nothing to undo", "Rien à annuler"),
+    ("Deleted {}, <u> to undo", "{} supprimée, <u> pour annuler"),
+    ("Pinned", "Épinglée"),
+    ("Unpinned", "Désépinglée"),
+    ("Marked as billable", "Marquée comme facturable"),
+    ("Marked as not billable", "Marquée comme non facturable"),
+    (
+        "Started working on the entry",
+        "Travail sur l'entrée commencé",
+    ),
+    (
+        "Stopped working on the entry",
+        "Travail sur l'entrée arrêté",
+    ),
+    ("New", "Nouveau"),
+    ("Quick Add", "Ajout rapide"),
+    ("Select", "Sélectionner"),
+    ("Pin", "Épingler"),
+    ("Undo", "Annuler"),
+    ("Stats", "Statistiques"),
+    ("Quit", "Quitter"),
+    ("Log", "Entrée"),
+    ("Modified", "Modifié"),
+    ("Created", "Créé"),
+    ("No entries match {}", "Aucune entrée ne correspond à {}"),
+    ("Nothing here yet", "Rien pour l'instant"),
+    ("Working on", "En cours"),
+    ("Add", "Ajouter"),
+    ("Cancel", "Annuler"),
+    ("Search", "Recherche"),
+    ("Apply", "Appliquer"),
+    ("Close", "Fermer"),
+    ("item {}/{}", "entrée {}/{}"),
+    // actions
+    ("New entry", "Nouvelle entrée"),
+    ("Quick add", "Ajout rapide"),
+    ("Open today's note", "Ouvrir la note du jour"),
+    ("Open selected entry", "Ouvrir l'entrée sélectionnée"),
+    ("Delete selected entry", "Supprimer l'entrée sélectionnée"),
+    (
+        "Pin or unpin selected entry",
+        "Épingler ou désépingler l'entrée sélectionnée",
+    ),
+    (
+        "Mark selected entry as billable or not",
+        "Marquer l'entrée sélectionnée comme facturable ou non",
+    ),
+    (
+        "Copy the short id of selected entry",
+        "Copier l'id courte de l'entrée sélectionnée",
+    ),
+    (
+        "Copy the full id of selected entry",
+        "Copier l'id complète de l'entrée sélectionnée",
+    ),
+    (
+        "Start or stop working on selected entry",
+        "Commencer ou arrêter le travail sur l'entrée sélectionnée",
+    ),
+    (
+        "Follow up on selected entry",
+        "Donner suite à l'entrée sélectionnée",
+    ),
+    (
+        "Undo last deletion or edit",
+        "Annuler la dernière suppression ou modification",
+    ),
+    ("Save all changes", "Enregistrer toutes les modifications"),
+    (
+        "The terminal is too small, lw needs at least {} columns and {} rows.",
+        "Le terminal est trop petit, lw a besoin d'au moins {} colonnes et {} lignes.",
+    ),
+    ("Down", "Bas"),
+    ("Up", "Haut"),
+    ("Top", "Début"),
+    ("Bottom", "Fin"),
+    ("Cycle row density", "Changer la densité des lignes"),
+    (
+        "Toggle sorting by date or alphabetically",
+        "Trier par date ou par ordre alphabétique",
+    ),
+    (
+        "Show a month, week, day or everything",
+        "Afficher un mois, une semaine, un jour ou tout",
+    ),
+    ("Show the previous period", "Afficher la période précédente"),
+    ("Show the next period", "Afficher la période suivante"),
+    (
+        "Search, e.g. tag:foo project:acme /deploy.*prod/ before:2024-06-01",
+        "Rechercher, p. ex. tag:foo project:acme /deploy.*prod/ before:2024-06-01",
+    ),
+    ("Filter by person", "Filtrer par personne"),
+    ("Clear the search", "Effacer la recherche"),
+    ("Statistics", "Statistiques"),
+    ("Week view", "Vue de la semaine"),
+    ("Switch to the previous view", "Revenir à la vue précédente"),
+    ("Export this week as HTML", "Exporter cette semaine en HTML"),
+    ("Review today's entries", "Passer en revue les entrées du jour"),
+    ("Command palette", "Palette de commandes"),
+    // command palette
+    ("Commands", "Commandes"),
+    ("Run", "Exécuter"),
+    ("No matching command", "Aucune commande correspondante"),
+    ("Exported the week to {}", "Semaine exportée vers {}"),
+    // review
+    ("Review", "Bilan"),
+    ("Nothing was logged today", "Rien n'a été noté aujourd'hui"),
+    ("Next", "Suivante"),
+    ("Previous", "Précédente"),
+    ("Edit", "Modifier"),
+    ("Done", "Terminé"),
+    ("Time", "Temps"),
+    ("Finish", "Terminer"),
+    ("Add time, e.g. 1h30m:", "Ajouter du temps, p. ex. 1h30m :"),
+    ("Not a duration: {}", "Pas une durée : {}"),
+    ("Copied the summary of the day", "Résumé de la journée copié"),
+    ("Tracked: {}", "Suivi : {}"),
+    // editor
+    ("Details", "Détails"),
+    ("characters", "caractères"),
+    ("Save", "Enregistrer"),
+    ("Split", "Scinder"),
+    ("Timestamp", "Horodatage"),
+    ("Links", "Liens"),
+    ("Fields", "Champs"),
+    ("Linked entries", "Entrées liées"),
+    ("follow-up of", "suite de"),
+    ("followed up by", "suivi par"),
+    ("Attachments", "Pièces jointes"),
+    ("yes", "oui"),
+    ("no", "non"),
+    ("invalid value for {}: {}", "valeur invalide pour {} : {}"),
+    ("{} · modified at {}", "{} · modifié le {}"),
+    ("created at {}", "créé le {}"),
+    (
+        "created at {}, worked on for {}",
+        "créé le {}, travaillé pendant {}",
+    ),
+    // dialogs
+    ("Unsaved Draft", "Brouillon non enregistré"),
+    ("Restore", "Restaurer"),
+    ("Discard", "Abandonner"),
+    (
+        "An edit from {} was not saved. Restore it?",
+        "Une modification du {} n'a pas été enregistrée. La restaurer ?",
+    ),
+    ("What's new in {}", "Nouveautés de {}"),
+    ("Scroll", "Défiler"),
+    (
+        "Your data file was updated",
+        "Votre fichier de données a été mis à jour",
+    ),
+    ("Unsaved Changes", "Modifications non enregistrées"),
+    ("Save and quit", "Enregistrer et quitter"),
+    (
+        "The log has changes that were not saved. Save them before quitting?",
+        "Le journal contient des modifications non enregistrées. Les enregistrer avant de quitter ?",
+    ),
+    ("Quit lw?", "Quitter lw ?"),
+    (
+        "Unsaved changes, press <ESC> again or <q> to quit",
+        "Modifications non enregistrées, appuyez à nouveau sur <ESC> ou sur <q> pour quitter",
+    ),
+    ("Duplicate Entry", "Entrée en double"),
+    ("Add anyway", "Ajouter quand même"),
+    (
+        "The same entry was added at {}. Add it again?",
+        "La même entrée a été ajoutée à {}. L'ajouter à nouveau ?",
+    ),
+    ("Saving Failed", "Échec de l'enregistrement"),
+    ("Retry", "Réessayer"),
+    ("Later", "Plus tard"),
+    (
+        "The changes are kept until they can be saved.",
+        "Les modifications sont conservées jusqu'à ce qu'elles puissent être enregistrées.",
+    ),
+    (
+        "The log could not be loaded",
+        "Le journal n'a pas pu être chargé",
+    ),
+    ("Set aside", "Mettre de côté"),
+    (
+        "{} is damaged. Set it aside to start without it, it is kept next to the original for recovery.",
+        "{} est endommagé. Mettez-le de côté pour démarrer sans lui, il est conservé à côté de l'original pour être récupéré.",
+    ),
+    (
+        "Fix the problem and retry, your data has not been changed.",
+        "Corrigez le problème et réessayez, vos données n'ont pas été modifiées.",
+    ),
+    (
+        "The damaged file was moved to {}.",
+        "Le fichier endommagé a été déplacé vers {}.",
+    ),
+    // week view and statistics
+    ("Week {} · {} – {}", "Semaine {} · {} – {}"),
+    ("Move", "Déplacer"),
+    ("Open", "Ouvrir"),
+    ("Week", "Semaine"),
+    ("Today", "Aujourd'hui"),
+    ("Go to", "Aller à"),
+    ("Go", "Aller"),
+    ("Back", "Retour"),
+    ("Time of day", "Moment de la journée"),
+    (
+        "{} entries, busiest hour {}",
+        "{} entrées, heure la plus active {}",
+    ),
+    ("Sort", "Trier"),
+    ("Projects, last {} days", "Projets, {} derniers jours"),
+    ("by tracked time", "par temps suivi"),
+    ("by entries", "par entrées"),
+    ("{} entries, {} tracked", "{} entrées, {} suivies"),
+];
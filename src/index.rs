@@ -0,0 +1,185 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::ops::Bound;
+
+use uuid::Uuid;
+
+use crate::log::Item;
+use crate::{collate, tag};
+
+/// Fewest hex digits a short id is abbreviated to, as in git.
+pub const SHORT_ID: usize = 7;
+
+/// Collation keys of an entry, computed once instead of on every search keystroke.
+#[derive(Debug, Clone, Default)]
+pub struct Entry {
+    /// Content folded for case and accent insensitive matching.
+    pub text: String,
+    pub tags: Vec<String>,
+    pub projects: Vec<String>,
+    pub people: Vec<String>,
+}
+
+impl Entry {
+    pub fn new(item: &Item) -> Self {
+        let keys = |markers: Vec<String>| markers.iter().map(|m| collate::key(m)).collect();
+        Self {
+            text: collate::key(&item.content()),
+            tags: keys(item.tags()),
+            projects: keys(item.projects()),
+            people: keys(item.people()),
+        }
+    }
+}
+
+/// Search index kept in sync with the log as entries are added, edited and removed.
+///
+/// Besides the per-entry keys it maps every marker to the entries carrying it, so tag, project
+/// and person filters only look at the entries they can match.
+#[derive(Debug, Clone, Default)]
+pub struct Index {
+    entries: HashMap<Uuid, Entry>,
+    /// Ids in order, so prefixes resolve to a range of them.
+    ids: BTreeSet<Uuid>,
+    /// How many neighbouring ids take how many digits to tell apart, so the abbreviation
+    /// follows the ids as they come and go.
+    digits: BTreeMap<usize, usize>,
+    markers: HashMap<(char, String), HashSet<Uuid>>,
+}
+
+impl Index {
+    pub fn new(logs: &[Item]) -> Self {
+        let mut index = Self::default();
+        for item in logs {
+            index.insert(item);
+        }
+        index
+    }
+
+    /// Indexes `item`, replacing what was indexed for it before.
+    pub fn insert(&mut self, item: &Item) {
+        self.remove(item.uuid());
+        let entry = Entry::new(item);
+        for (prefix, keys) in [
+            (tag::TAG, &entry.tags),
+            (tag::PROJECT, &entry.projects),
+            (tag::PERSON, &entry.people),
+        ] {
+            for key in keys {
+                self.markers
+                    .entry((prefix, key.clone()))
+                    .or_default()
+                    .insert(item.uuid());
+            }
+        }
+        self.entries.insert(item.uuid(), entry);
+        let (before, after) = self.neighbours(item.uuid());
+        if let (Some(before), Some(after)) = (before, after) {
+            self.unpair(before, after);
+        }
+        if let Some(before) = before {
+            self.pair(before, item.uuid());
+        }
+        if let Some(after) = after {
+            self.pair(item.uuid(), after);
+        }
+        self.ids.insert(item.uuid());
+    }
+
+    pub fn remove(&mut self, id: Uuid) {
+        if self.ids.remove(&id) {
+            let (before, after) = self.neighbours(id);
+            if let Some(before) = before {
+                self.unpair(before, id);
+            }
+            if let Some(after) = after {
+                self.unpair(id, after);
+            }
+            if let (Some(before), Some(after)) = (before, after) {
+                self.pair(before, after);
+            }
+        }
+        let Some(entry) = self.entries.remove(&id) else {
+            return;
+        };
+        for (prefix, keys) in [
+            (tag::TAG, entry.tags),
+            (tag::PROJECT, entry.projects),
+            (tag::PERSON, entry.people),
+        ] {
+            for key in keys {
+                let marker = (prefix, key);
+                if let Some(ids) = self.markers.get_mut(&marker) {
+                    ids.remove(&id);
+                    if ids.is_empty() {
+                        self.markers.remove(&marker);
+                    }
+                }
+            }
+        }
+    }
+
+    /// The indexed ids right before and after `id`, leaving out `id` itself.
+    fn neighbours(&self, id: Uuid) -> (Option<Uuid>, Option<Uuid>) {
+        let before = self.ids.range(..id).next_back().copied();
+        let after = self
+            .ids
+            .range((Bound::Excluded(id), Bound::Unbounded))
+            .next()
+            .copied();
+        (before, after)
+    }
+
+    fn pair(&mut self, a: Uuid, b: Uuid) {
+        *self.digits.entry(digits(a, b)).or_default() += 1;
+    }
+
+    fn unpair(&mut self, a: Uuid, b: Uuid) {
+        let digits = digits(a, b);
+        if let Some(count) = self.digits.get_mut(&digits) {
+            *count -= 1;
+            if *count == 0 {
+                self.digits.remove(&digits);
+            }
+        }
+    }
+
+    pub fn get(&self, id: Uuid) -> Option<&Entry> {
+        self.entries.get(&id)
+    }
+
+    /// Entries carrying the `<prefix>name` marker, ignoring case and accents.
+    pub fn tagged(&self, prefix: char, name: &str) -> Option<&HashSet<Uuid>> {
+        self.markers.get(&(prefix, collate::key(name)))
+    }
+
+    /// Length of the shortest prefix that tells every indexed id apart, at least [`SHORT_ID`].
+    pub fn abbrev(&self) -> usize {
+        self.digits
+            .last_key_value()
+            .map_or(0, |(digits, _)| *digits)
+            .clamp(SHORT_ID, 32)
+    }
+
+    /// Ids starting with `prefix`, ignoring case and dashes.
+    pub fn resolve(&self, prefix: &str) -> Vec<Uuid> {
+        let hex: String = prefix.chars().filter(|c| *c != '-').collect();
+        if hex.is_empty() || hex.len() > 32 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Vec::new();
+        }
+        let low = u128::from_str_radix(&format!("{hex:0<32}"), 16);
+        let high = u128::from_str_radix(&format!("{hex:f<32}"), 16);
+        match (low, high) {
+            (Ok(low), Ok(high)) => self
+                .ids
+                .range(Uuid::from_u128(low)..=Uuid::from_u128(high))
+                .copied()
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// Hex digits of a prefix that tells `a` and `b` apart.
+fn digits(a: Uuid, b: Uuid) -> usize {
+    (a.as_u128() ^ b.as_u128()).leading_zeros() as usize / 4 + 1
+}
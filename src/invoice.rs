@@ -0,0 +1,125 @@
+use std::fmt::Write;
+
+use chrono::{Datelike, NaiveDate};
+use color_eyre::{Result, eyre::eyre};
+
+use crate::log::Item;
+use crate::{collate, editor, tag};
+
+/// Longest description printed per line, cut at a word boundary.
+const DESCRIPTION_LENGTH: usize = 60;
+
+/// The billable entries of a month, optionally limited to one project.
+#[derive(Debug, Clone)]
+pub struct Invoice<'a> {
+    project: Option<String>,
+    /// First day of the month.
+    month: NaiveDate,
+    /// Price per hour, without it only hours are listed.
+    rate: Option<f64>,
+    entries: Vec<&'a Item>,
+}
+
+impl<'a> Invoice<'a> {
+    pub fn new(
+        logs: &'a [Item],
+        project: Option<&str>,
+        month: NaiveDate,
+        rate: Option<f64>,
+    ) -> Self {
+        let project = project.map(|p| p.trim_start_matches(tag::PROJECT).to_owned());
+        let mut entries: Vec<&Item> = logs
+            .iter()
+            .filter(|item| {
+                let created = item.created().date_naive();
+                item.billable()
+                    && created.year() == month.year()
+                    && created.month() == month.month()
+                    && project.as_ref().is_none_or(|project| {
+                        let project = collate::key(project);
+                        item.projects().iter().any(|p| collate::key(p) == project)
+                    })
+            })
+            .collect();
+        entries.sort_by_key(|item| item.created());
+        Self {
+            project,
+            month,
+            rate,
+            entries,
+        }
+    }
+
+    pub fn entries(&self) -> &[&'a Item] {
+        &self.entries
+    }
+
+    /// Billable hours of the whole month.
+    pub fn hours(&self) -> f64 {
+        self.entries.iter().map(|item| hours(item)).sum()
+    }
+
+    /// A plain text breakdown with a line per entry, ready to be pasted into an invoice.
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        let _ = write!(text, "Invoice for {}", self.month.format("%B %Y"));
+        if let Some(ref project) = self.project {
+            let _ = write!(text, ", {}{project}", tag::PROJECT);
+        }
+        text.push('\n');
+        if let Some(rate) = self.rate {
+            let _ = writeln!(text, "Rate: {rate:.2} per hour");
+        }
+        if self.entries.is_empty() {
+            text.push_str("\nNo billable entries.\n");
+            return text;
+        }
+
+        let amount = |hours: f64| {
+            self.rate
+                .map(|rate| format!("  {:>10.2}", hours * rate))
+                .unwrap_or_default()
+        };
+        let _ = writeln!(
+            text,
+            "\n{:<10}  {:>6}{}  Description",
+            "Date",
+            "Hours",
+            if self.rate.is_some() {
+                "      Amount"
+            } else {
+                ""
+            }
+        );
+        for item in &self.entries {
+            let content = item.content();
+            let (line, _) = editor::first_line(&content);
+            let _ = writeln!(
+                text,
+                "{:<10}  {:>6.2}{}  {}",
+                item.created().format("%Y-%m-%d"),
+                hours(item),
+                amount(hours(item)),
+                editor::summarize(line, DESCRIPTION_LENGTH)
+            );
+        }
+        let _ = writeln!(
+            text,
+            "{:<10}  {:>6.2}{}",
+            "Total",
+            self.hours(),
+            amount(self.hours())
+        );
+        text
+    }
+}
+
+fn hours(item: &Item) -> f64 {
+    item.tracked().num_seconds() as f64 / 3600.0
+}
+
+/// Parses a month given as `YYYY-MM` into its first day.
+pub fn month(value: &str) -> Result<NaiveDate> {
+    NaiveDate::parse_from_str(&format!("{value}-01"), "%Y-%m-%d")
+        .map_err(|_| eyre!("invalid month {value}, expected YYYY-MM"))
+}
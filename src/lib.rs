@@ -1,13 +1,23 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
 use std::sync::OnceLock;
-use std::{fs, path::PathBuf};
+use std::time::{Duration, Instant};
 
 use color_eyre::{Result, eyre::eyre};
 
-use ratatui::crossterm::event::{KeyEvent, KeyModifiers};
+use ratatui::crossterm::event::{
+    DisableBracketedPaste, EnableBracketedPaste, KeyEvent, KeyEventKind, KeyModifiers,
+};
+use ratatui::crossterm::execute;
+use ratatui::crossterm::terminal::{EnterAlternateScreen, enable_raw_mode};
 use ratatui::layout::Flex;
 use ratatui::prelude::*;
 
-use ratatui::widgets::{Cell, Clear, HighlightSpacing, Paragraph, Row, Table, TableState};
+use ratatui::widgets::{
+    Cell, Clear, HighlightSpacing, Paragraph, Row, Scrollbar, ScrollbarOrientation, ScrollbarState,
+    Table, TableState,
+};
 use ratatui::{
     DefaultTerminal, Frame,
     crossterm::event::{self, Event, KeyCode},
@@ -17,22 +27,124 @@ use ratatui::{
     widgets::{Block, Widget},
 };
 use serde::{Deserialize, Serialize};
+use unicode_width::UnicodeWidthStr;
 
+use crate::action::{Action, Palette};
+use crate::attachment::Attachment;
+use crate::i18n::t;
+use crate::index::Index;
 use crate::log::Item;
+use crate::query::Query;
+use crate::rule::Rules;
+use crate::selector::Selector;
+use crate::settings::{
+    Compression, Density, DuplicateAction, FieldType, LengthPolicy, PreviewMode, Settings,
+    SortOrder,
+};
+use crate::shard::Shards;
+use crate::undo::{Change, Journal};
 
+pub mod action;
+pub mod attachment;
+pub mod audit;
+pub mod backup;
+pub mod browse;
+pub mod changelog;
+pub mod cli;
+pub mod clipboard;
+pub mod collate;
+pub mod daemon;
+pub mod dates;
+pub mod draft;
+pub mod editor;
+pub mod export;
+pub mod focus;
+pub mod goal;
+pub mod hook;
+pub mod i18n;
+pub mod index;
+pub mod invoice;
 pub mod log;
+pub mod merge;
+pub mod paths;
+pub mod popup;
+pub mod query;
+pub mod recover;
+pub mod review;
+pub mod rows;
+pub mod rule;
+pub mod selector;
+#[cfg(feature = "serve")]
+pub mod serve;
+pub mod settings;
+pub mod shard;
+pub mod shell;
+pub mod signal;
+#[cfg(feature = "slack")]
+pub mod slack;
+#[cfg(feature = "spellcheck")]
+pub mod spell;
+pub mod stats;
+pub mod storage;
+#[cfg(feature = "sync")]
+pub mod sync;
+pub mod tag;
+pub mod toast;
+pub mod undo;
+pub mod vault;
+pub mod week;
 
 pub static APP_NAME: &str = "lw";
 static CONFIG_PATH: OnceLock<PathBuf> = OnceLock::new();
-static COLOR_PRIMARY: Color = Color::Rgb(51, 217, 178);
-static COLOR_PRIMARY_DARK: Color = Color::Rgb(33, 140, 116);
-static COLOR_SECONDARY: Color = Color::Rgb(52, 172, 224);
-static COLOR_TERTIARY: Color = Color::Rgb(247, 241, 227);
-static COLOR_TERTIARY_DARK: Color = Color::Rgb(132, 129, 122);
+static TICK_RATE: Duration = Duration::from_millis(250);
+static DRAFT_INTERVAL: Duration = Duration::from_secs(2);
+static BADGES_WIDTH: u16 = 10;
+static TIMESTAMP_WIDTH: u16 = 20;
+
+/// Content of the entry a new log starts with in the TUI.
+static WELCOME: &str = "Welcome to lw, your personal work log!
+Press <o> for a new entry or <i> to quickly add one, <e> to open the selected entry and <CTRL-p> to find every command. <CTRL-Enter> saves the entry you are editing.
+Mention #tags, +projects and @people to find entries later with </>.
+Delete this entry with <d> <d> once you are done. #lw";
+
+pub(crate) static COLOR_PRIMARY: Color = Color::Rgb(51, 217, 178);
+pub(crate) static COLOR_PRIMARY_DARK: Color = Color::Rgb(33, 140, 116);
+pub(crate) static COLOR_SECONDARY: Color = Color::Rgb(52, 172, 224);
+pub(crate) static COLOR_TERTIARY: Color = Color::Rgb(247, 241, 227);
+pub(crate) static COLOR_TERTIARY_DARK: Color = Color::Rgb(132, 129, 122);
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum View {
+    #[default]
+    Log,
+    Stats,
+    Week,
+}
+
+/// Outcome of [`App::add_unique`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Added {
+    New,
+    /// The entry repeated this one and was dropped.
+    Skipped(Item),
+    /// The entry repeated this one and replaced its content.
+    Merged(Item),
+    /// The entry repeats `duplicate`, and whether to add it is up to the user.
+    Confirm {
+        duplicate: Item,
+        item: Item,
+    },
+}
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct App {
+    #[serde(default)]
+    version: Option<String>,
     logs: Vec<Item>,
+    #[serde(default)]
+    tombstones: Vec<merge::Tombstone>,
+    #[serde(default)]
+    current: Option<focus::Current>,
     #[serde(skip)]
     exit: bool,
     #[serde(skip)]
@@ -41,7 +153,119 @@ pub struct App {
     delete: Option<usize>,
     #[serde(skip)]
     state: TableState,
+    #[serde(skip)]
+    rows: rows::Rows,
+    #[serde(skip)]
+    cursor: usize,
+    #[serde(skip)]
+    completion: Option<usize>,
+    #[cfg(feature = "spellcheck")]
+    #[serde(skip)]
+    spelling: spell::Spelling,
+    #[serde(skip)]
+    link_focus: Option<usize>,
+    #[serde(skip)]
+    project_sort: stats::ProjectSort,
+    /// Size of the editor, remembered across runs.
+    #[serde(skip)]
+    popup: popup::Size,
+    /// The terminal was too small to draw in, keys are ignored until it grows.
+    #[serde(skip)]
+    too_small: bool,
+    /// Value being typed into the custom field focused in the editor.
+    #[serde(skip)]
+    field_input: Option<String>,
+    #[serde(skip)]
+    quick_add: Option<String>,
+    #[serde(skip)]
+    shards: Shards,
+    #[serde(skip)]
+    index: Index,
+    #[serde(skip)]
+    filter: Option<Query>,
+    #[serde(skip)]
+    filter_input: Option<String>,
+    #[serde(skip)]
+    filter_error: Option<String>,
+    #[serde(skip)]
+    previous_filter: Option<Query>,
+    #[serde(skip)]
+    view: View,
+    #[serde(skip)]
+    previous_view: View,
+    #[serde(skip)]
+    week: week::Cursor,
+    /// Day typed into the go to prompt of the week view.
+    #[serde(skip)]
+    jump: Option<String>,
+    #[serde(skip)]
+    jump_error: Option<String>,
+    #[serde(skip)]
+    draft: Option<Item>,
+    #[serde(skip)]
+    restore: Option<Item>,
+    #[serde(skip)]
+    settings: Settings,
+    #[serde(skip)]
+    rules: Rules,
+    #[serde(skip)]
+    journal: Journal,
+    #[serde(skip)]
+    density: Density,
+    #[serde(skip)]
+    sort_order: SortOrder,
+    /// The day, week or month the table is limited to, `None` for the whole log.
+    #[serde(skip)]
+    period: Option<browse::Period>,
+    #[serde(skip)]
+    palette: Option<Palette>,
+    #[serde(skip)]
+    review: Option<review::Review>,
+    /// The summary of the last review, printed once the TUI is closed.
+    #[serde(skip)]
+    summary: Option<String>,
+    #[serde(skip)]
+    whats_new: Option<String>,
+    #[serde(skip)]
+    whats_new_scroll: u16,
+    #[serde(skip)]
+    read_only: bool,
+    #[serde(skip)]
+    dirty: bool,
+    #[serde(skip)]
+    confirm_quit: bool,
+    /// `<ESC>` was pressed once in the main view with unsaved changes.
+    #[serde(skip)]
+    escape_pending: bool,
+    #[serde(skip)]
+    duplicate: Option<(Item, Item)>,
+    /// Outcomes of the recent actions, shown until they expire.
+    #[serde(skip)]
+    toasts: toast::Toasts,
+    /// The data file was created on this start, the TUI then greets with a welcome entry.
+    #[serde(skip)]
+    first_run: bool,
+    /// Why the last save failed. The changes stay in memory and saving can be retried.
+    #[serde(skip)]
+    save_error: Option<String>,
+    /// Nothing is read from or written to disk, see [`App::in_memory`].
+    #[serde(skip)]
+    in_memory: bool,
+    /// Changes since the last save that hooks are configured for.
+    #[serde(skip)]
+    hook_changes: Vec<hook::Change>,
+    #[serde(skip)]
+    audit: audit::Trail,
 }
+/// What is written to the data file itself. The entries live in the monthly shards.
+#[derive(Serialize)]
+struct Header<'a> {
+    version: &'a Option<String>,
+    logs: &'a [Item],
+    tombstones: &'a [merge::Tombstone],
+    current: &'a Option<focus::Current>,
+}
+
 impl Default for App {
     #[allow(clippy::expect_used)]
     fn default() -> Self {
@@ -51,83 +275,427 @@ impl Default for App {
 }
 impl App {
     pub fn new(config: PathBuf) -> Result<Self> {
-        if config.exists()
-            && let Ok(v) = fs::read_to_string(config)
+        Self::load(config, false)
+    }
+
+    /// Loads the log read-only with default settings and without the undo
+    /// journal, pending drafts or the changelog screen.
+    pub fn safe_mode(config: PathBuf) -> Result<Self> {
+        Self::load(config, true)
+    }
+
+    /// Reads a data file and every shard stored next to it, e.g. another copy of the log to
+    /// merge.
+    pub fn read(config: &Path) -> Result<Self> {
+        let mut app = Self::parse(config)?;
+        app.load_all()?;
+        Ok(app)
+    }
+
+    /// Reads a data file with only the current month of entries, or at least the latest
+    /// month that has any.
+    fn parse(config: &Path) -> Result<Self> {
+        let content = storage::read(config)?;
+        let mut app: Self =
+            serde_json::from_str(&content).map_err(|e| storage::Corrupt::parse(config, e))?;
+        app.shards = Shards::open(config)?;
+        if app.logs.is_empty() {
+            let current = shard::month(&Item::new());
+            app.logs = app.shards.load(&current)?;
+            while app.logs.is_empty() && !app.shards.is_complete() {
+                app.logs = app.shards.load_older()?;
+            }
+        } else {
+            app.shards.adopt(&app.logs);
+        }
+        if let Some(ref current) = app.current
+            && !app.logs.iter().any(|i| i.id() == current.id)
         {
-            let mut app: Self = serde_json::from_str(&v)?;
+            let items = app.shards.load_all()?;
+            app.logs.extend(items);
+        }
+        app.index = Index::new(&app.logs);
+        Ok(app)
+    }
 
-            app.logs.sort_by_key(|l| std::cmp::Reverse(l.created()));
-            return Ok(app);
+    /// Writes a data file without entries to `config`, e.g. in place of a corrupt one.
+    pub fn init(config: &Path) -> Result<()> {
+        if let Some(dir) = config.parent() {
+            paths::create(dir)?;
         }
-        Err(eyre!("failed to read config"))
+        let header = Header {
+            version: &Some(changelog::VERSION.to_owned()),
+            logs: &[],
+            tombstones: &[],
+            current: &None,
+        };
+        storage::write(config, &header, Compression::None)
     }
-    #[allow(clippy::expect_used)]
-    pub fn config_path() -> &'static PathBuf {
-        CONFIG_PATH.get_or_init(|| {
-            if cfg!(windows) {
-                let base = std::env::var("APPDATA").unwrap_or_else(|_| ".".to_string());
-                let dir = PathBuf::from(base).join(APP_NAME);
 
-                if !dir.exists() {
-                    fs::create_dir_all(&dir).expect("failed to create app directory");
-                }
-                dir.join("config.json")
-            } else {
-                let base = std::env::var("HOME").unwrap_or_else(|_| "/tmp".to_string());
-                let dir = PathBuf::from(base).join(".config").join(APP_NAME);
-                if !dir.exists() {
-                    fs::create_dir_all(&dir).expect("failed to create app directory");
-                }
-                dir.join("config.json")
+    fn load(config: PathBuf, safe_mode: bool) -> Result<Self> {
+        let first_run = !config.exists() && !safe_mode;
+        if first_run {
+            Self::init(&config)?;
+        }
+        if config.exists() {
+            let mut app = Self::parse(&config)?;
+            app.first_run = first_run;
+            if safe_mode {
+                app.read_only = true;
+                app.sort();
+                return Ok(app);
             }
-        })
+            app.settings = Settings::load()?;
+            app.rules = Rules::new(&app.settings.rules)?;
+            app.settings.apply_locale();
+            app.journal = Journal::load()?;
+            app.density = app.settings.density;
+            app.sort_order = app.settings.sort;
+            app.popup = popup::load().unwrap_or_default();
+
+            let version = app
+                .version
+                .clone()
+                .unwrap_or_else(|| changelog::UNVERSIONED.to_owned());
+            if changelog::is_outdated(&version) {
+                app.whats_new = Some(version);
+            }
+
+            app.sort();
+            return Ok(app);
+        }
+        Err(eyre!("there is no log at {} yet", config.display()))
+    }
+
+    /// A log of `logs` that lives only in memory, with default settings and without a spell
+    /// checker. Saving it writes nothing, e.g. to drive the TUI from tests.
+    pub fn in_memory(logs: Vec<Item>) -> Self {
+        #[allow(clippy::expect_used)]
+        let mut app: Self =
+            serde_json::from_str(r#"{"logs":[]}"#).expect("an empty log deserializes");
+        app.version = Some(changelog::VERSION.to_owned());
+        app.logs = logs;
+        app.index = Index::new(&app.logs);
+        app.in_memory = true;
+        #[cfg(feature = "spellcheck")]
+        {
+            app.spelling = spell::Spelling::off();
+        }
+        app.sort();
+        app.state.select_next();
+        app
+    }
+
+    /// Loads the next older month of entries, returning whether there was one.
+    pub fn load_older(&mut self) -> Result<bool> {
+        if self.shards.is_complete() {
+            return Ok(false);
+        }
+        let items = self.shards.load_older()?;
+        self.extend(items);
+        Ok(true)
+    }
+
+    /// Loads every entry, for operations that need the whole history.
+    pub fn load_all(&mut self) -> Result<()> {
+        let items = self.shards.load_all()?;
+        self.extend(items);
+        Ok(())
+    }
+
+    /// Loads the month `item` belongs to, before adding or restoring an entry there.
+    pub fn load_month(&mut self, item: &Item) -> Result<()> {
+        let items = self.shards.load(&shard::month(item))?;
+        self.extend(items);
+        Ok(())
+    }
+
+    fn extend(&mut self, items: Vec<Item>) {
+        if items.is_empty() {
+            return;
+        }
+        let selected = self.selected().map(Item::id);
+        for item in &items {
+            self.index.insert(item);
+        }
+        self.logs.extend(items);
+        self.sort();
+        if let Some(id) = selected {
+            self.select(&id);
+        }
+    }
+    /// Shows `message` in a toast, e.g. the outcome of something done before the TUI started.
+    pub fn notify(&mut self, message: impl Into<String>) {
+        self.toasts.info(message);
+    }
+
+    pub fn read_only(&self) -> bool {
+        self.read_only
+    }
+
+    /// Whether the log was changed since it was last saved.
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
     }
 
-    fn draw(&mut self, frame: &mut Frame) {
-        self.render(frame.area(), frame.buffer_mut());
+    /// The data file, `config.json` in the directory of [`paths::config_dir`].
+    pub fn config_path() -> &'static PathBuf {
+        CONFIG_PATH.get_or_init(|| paths::config_dir().0.join("config.json"))
+    }
+
+    /// Renders the current view with its dialogs and toasts into `frame`.
+    pub fn draw(&mut self, frame: &mut Frame) {
+        self.too_small = popup::too_small(frame.area());
+        if self.too_small {
+            draw_too_small(frame);
+            return;
+        }
+        match self.view {
+            View::Stats => {
+                frame.render_widget(
+                    stats::StatsView {
+                        logs: &self.logs,
+                        today: chrono::Local::now().date_naive(),
+                        sort: self.project_sort,
+                        colors: &self.settings.colors,
+                    },
+                    frame.area(),
+                );
+            }
+            View::Week => {
+                let area = match self.jump {
+                    Some(ref input) => {
+                        let [week_area, input_area] =
+                            Layout::vertical([Constraint::Min(0), Constraint::Length(3)])
+                                .areas(frame.area());
+                        let mut title = Line::from(Span::styled(
+                            format!(" {} ", t("Go to")),
+                            Style::default().bold().fg(COLOR_SECONDARY),
+                        ));
+                        if let Some(ref error) = self.jump_error {
+                            title.push_span(Span::styled(
+                                format!("{error} "),
+                                Style::default().bold().fg(Color::LightRed),
+                            ));
+                        }
+                        let block = Block::bordered()
+                            .title(title)
+                            .title_bottom(key_hints(
+                                &[("Go", "<Enter>"), ("Cancel", "<CTRL-c> | <ESC>")],
+                                COLOR_PRIMARY,
+                            ))
+                            .title_style(Style::default().bold().fg(Color::White))
+                            .border_style(Color::White);
+                        frame
+                            .render_widget(Paragraph::new(input.as_str()).block(block), input_area);
+                        week_area
+                    }
+                    None => frame.area(),
+                };
+                frame.render_widget(
+                    week::WeekView {
+                        logs: &self.logs,
+                        cursor: self.week,
+                        goal: self.settings.goal.as_ref(),
+                    },
+                    area,
+                );
+            }
+            View::Log => self.render(frame.area(), frame.buffer_mut()),
+        }
+        if let Some(ref palette) = self.palette {
+            self.draw_palette(frame, palette);
+        }
+        if let Some(ref review) = self.review {
+            self.draw_review(frame, review);
+        }
+        if let Some(ref item) = self.restore {
+            self.draw_restore(frame, item);
+        }
+        if let Some(ref version) = self.whats_new {
+            self.draw_whats_new(frame, version);
+        }
+        if self.confirm_quit {
+            self.draw_confirm_quit(frame);
+        }
+        if let Some((ref duplicate, ref item)) = self.duplicate {
+            self.draw_duplicate(frame, duplicate, item);
+        }
+        if let Some(ref error) = self.save_error {
+            self.draw_save_error(frame, error);
+        }
         if let Some(ref item) = self.edit {
+            let mut title = vec![Span::styled(
+                t("Details"),
+                Style::default().bold().fg(COLOR_SECONDARY),
+            )];
+            if let Some(max) = self.settings.max_length {
+                let len = editor::len(&item.content());
+                if len > max {
+                    title.push(Span::styled(
+                        format!(" {len}/{max} {} ", t("characters")),
+                        Style::default().bold().fg(Color::LightRed),
+                    ));
+                }
+            }
+            let linked = self.linked(item);
+            let mut hints = vec![
+                Span::raw(format!(" {} ", t("Save"))),
+                Span::styled(
+                    "<CTRL-Enter> | <CTRL-o>",
+                    Style::default()
+                        .fg(COLOR_PRIMARY)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(format!(" {} ", t("Split"))),
+                Span::styled(
+                    "<CTRL-s>",
+                    Style::default()
+                        .fg(COLOR_PRIMARY)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(format!(" {} ", t("Timestamp"))),
+                Span::styled(
+                    "<CTRL-t>",
+                    Style::default()
+                        .fg(COLOR_PRIMARY)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(format!(" {} ", t("Cancel"))),
+                Span::styled(
+                    "<CTRL-c> | <ESC>",
+                    Style::default()
+                        .fg(COLOR_PRIMARY)
+                        .add_modifier(Modifier::BOLD),
+                ),
+            ];
+            let fields = self.fields(item);
+            if !linked.is_empty() || !item.attachments().is_empty() || !fields.is_empty() {
+                hints.push(Span::raw(format!(
+                    " {} ",
+                    t(if fields.is_empty() { "Links" } else { "Fields" })
+                )));
+                hints.push(Span::styled(
+                    "<Tab>",
+                    Style::default()
+                        .fg(COLOR_PRIMARY)
+                        .add_modifier(Modifier::BOLD),
+                ));
+            }
             let block = Block::bordered()
-                .title(Span::styled(
-                    "Details",
-                    Style::default().bold().fg(COLOR_SECONDARY),
-                ))
-                .title_bottom(Line::from(vec![
-                    Span::raw(" Save "),
-                    Span::styled(
-                        "<CTRL-Enter> | <CTRL-o>",
-                        Style::default()
-                            .fg(COLOR_PRIMARY)
-                            .add_modifier(Modifier::BOLD),
-                    ),
-                    Span::raw(" Cancel "),
-                    Span::styled(
-                        "<CTRL-c> | <ESC>",
-                        Style::default()
-                            .fg(COLOR_PRIMARY)
-                            .add_modifier(Modifier::BOLD),
-                    ),
-                ]))
+                .title(Line::from(title))
+                .title_bottom(Line::from(hints))
                 .title_style(Style::default().bold().fg(Color::White));
 
-            let area = popup_area(frame.area(), 90, 90);
+            let area = self.popup.area(frame.area());
 
             frame.render_widget(Clear, area);
 
-            let outer = Layout::default()
-                .direction(Direction::Vertical)
-                .constraints(vec![Constraint::Percentage(50), Constraint::Percentage(50)])
-                .split(area);
+            // the details below the text take the lines they need, up to half of the editor
+            let details = [linked.len(), item.attachments().len(), fields.len()]
+                .into_iter()
+                .filter(|count| *count > 0)
+                .map(|count| count + 2)
+                .sum::<usize>()
+                + 1;
+            let outer = Layout::vertical([
+                Constraint::Min(0),
+                Constraint::Length((details as u16).min(area.height / 2)),
+            ])
+            .split(area);
+
+            let [timestamps, links_area] =
+                Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(outer[1]);
 
             let inner = Layout::default()
                 .direction(Direction::Horizontal)
                 .constraints(vec![Constraint::Percentage(50), Constraint::Percentage(50)])
-                .split(outer[1]);
+                .split(timestamps);
+
+            if !linked.is_empty() || !item.attachments().is_empty() || !fields.is_empty() {
+                let mut lines = Vec::new();
+                let focus = |i| {
+                    let style = Style::default().fg(COLOR_TERTIARY);
+                    if self.link_focus == Some(i) {
+                        style.fg(COLOR_PRIMARY).add_modifier(Modifier::REVERSED)
+                    } else {
+                        style
+                    }
+                };
+                if !linked.is_empty() {
+                    lines.push(Line::from(""));
+                    lines.push(Line::from(Span::styled(
+                        t("Linked entries"),
+                        Style::default().fg(COLOR_SECONDARY).bold(),
+                    )));
+                }
+                for (i, (relation, linked)) in linked.iter().enumerate() {
+                    lines.push(Line::from(Span::styled(
+                        editor::truncate_to_width(
+                            &format!(
+                                "{} {}: {}",
+                                t(relation),
+                                linked.created().format("%Y-%m-%d %H:%M"),
+                                self.preview(linked)
+                            ),
+                            usize::from(links_area.width),
+                        ),
+                        focus(i),
+                    )));
+                }
+                if !item.attachments().is_empty() {
+                    lines.push(Line::from(""));
+                    lines.push(Line::from(Span::styled(
+                        t("Attachments"),
+                        Style::default().fg(COLOR_SECONDARY).bold(),
+                    )));
+                }
+                for (i, attachment) in item.attachments().iter().enumerate() {
+                    lines.push(Line::from(Span::styled(
+                        editor::truncate_to_width(
+                            &format!("📎 {}", attachment.name()),
+                            usize::from(links_area.width),
+                        ),
+                        focus(linked.len() + i),
+                    )));
+                }
+                if !fields.is_empty() {
+                    lines.push(Line::from(""));
+                    lines.push(Line::from(Span::styled(
+                        t("Fields"),
+                        Style::default().fg(COLOR_SECONDARY).bold(),
+                    )));
+                }
+                let offset = linked.len() + item.attachments().len();
+                for (i, (name, kind)) in fields.iter().enumerate() {
+                    let focused = self.link_focus == Some(offset + i);
+                    let value = match (&self.field_input, item.field(name)) {
+                        (Some(input), _) if focused => format!("{input}▏"),
+                        (_, Some(value)) if *kind == FieldType::Bool => {
+                            t(if value == "true" { "yes" } else { "no" }).to_owned()
+                        }
+                        (_, Some(value)) => value.to_owned(),
+                        (_, None) => "–".to_owned(),
+                    };
+                    lines.push(Line::from(Span::styled(
+                        editor::truncate_to_width(
+                            &format!("{name}: {value}"),
+                            usize::from(links_area.width),
+                        ),
+                        focus(offset + i),
+                    )));
+                }
+                frame.render_widget(Paragraph::new(lines), links_area);
+            }
 
             frame.render_widget(
                 Paragraph::new(
-                    Text::from(format!(
-                        "modified at {}",
-                        item.modified().format("%Y-%m-%d %H:%M:%S")
+                    Text::from(i18n::fill(
+                        "{} · modified at {}",
+                        &[
+                            &self.short_id(item),
+                            &item.modified().format("%Y-%m-%d %H:%M:%S").to_string(),
+                        ],
                     ))
                     .style(Style::default().fg(COLOR_SECONDARY).bold())
                     .right_aligned(),
@@ -138,193 +706,2369 @@ impl App {
 
             frame.render_widget(
                 Paragraph::new(
-                    Text::from(format!(
-                        "created at {}",
-                        item.created().format("%Y-%m-%d %H:%M:%S")
-                    ))
+                    Text::from(match item.tracked() {
+                        tracked if tracked.is_zero() => i18n::fill(
+                            "created at {}",
+                            &[&item.created().format("%Y-%m-%d %H:%M:%S").to_string()],
+                        ),
+                        tracked => i18n::fill(
+                            "created at {}, worked on for {}",
+                            &[
+                                &item.created().format("%Y-%m-%d %H:%M:%S").to_string(),
+                                &focus::format(tracked),
+                            ],
+                        ),
+                    })
                     .style(Style::default().fg(COLOR_SECONDARY).bold()),
                 ),
                 inner[0],
             );
 
-            let content: String = item.content().to_owned();
-            let v: Vec<Line> = content
-                .split("\n")
-                .enumerate()
-                .map(|(i, c)| {
-                    let mut t = vec![Span::from(c)];
-                    if i >= content.split("\n").count() - 1 {
-                        t.push(
-                            Span::from("_")
-                                .patch_style(Style::new().add_modifier(Modifier::RAPID_BLINK)),
-                        );
-                    }
-                    Line::from(t)
-                })
+            let content = item.content();
+            let inner = block.inner(outer[0]);
+            let rows = editor::wrap(&content, usize::from(inner.width));
+            let (row, column) = editor::cursor_position(&content, &rows, self.cursor);
+            let scroll = row.saturating_sub(usize::from(inner.height.saturating_sub(1)));
+            #[cfg(feature = "spellcheck")]
+            let v: Vec<Line> = {
+                let misspelled = self
+                    .spelling
+                    .misspelled(
+                        self.settings
+                            .spellcheck
+                            .as_deref()
+                            .unwrap_or(spell::DEFAULT_COMMAND),
+                        &content,
+                    )
+                    .unwrap_or_else(|e| {
+                        self.toasts.error(e.to_string());
+                        Vec::new()
+                    });
+                rows.iter()
+                    .map(|r| spell::underline(&content, r, &misspelled))
+                    .collect()
+            };
+            #[cfg(not(feature = "spellcheck"))]
+            let v: Vec<Line> = rows
+                .iter()
+                .map(|r| Line::from(&content[r.clone()]))
                 .collect();
 
             frame.render_widget(
-                Paragraph::new(v)
-                    .block(block)
-                    .wrap(ratatui::widgets::Wrap { trim: false }),
+                Paragraph::new(v).block(block).scroll((scroll as u16, 0)),
                 outer[0],
             );
+            let cursor = Position::new(
+                inner.x + (column as u16).min(inner.width.saturating_sub(1)),
+                inner.y + (row - scroll) as u16,
+            );
+            if let Some(selected) = self.completion
+                && let Some((start, candidates)) = self.completions(item)
+            {
+                let word = &content[start..self.cursor.min(content.len())];
+                self.draw_completions(frame, cursor, word, &candidates, selected);
+            }
+            #[cfg(feature = "spellcheck")]
+            if let Some(ref popup) = self.spelling.popup {
+                let (row, column) = editor::cursor_position(&content, &rows, popup.word.start);
+                let anchor = Position::new(
+                    inner.x + (column as u16).min(inner.width.saturating_sub(1)),
+                    inner.y + row.saturating_sub(scroll) as u16,
+                );
+                let lines = popup
+                    .candidates
+                    .iter()
+                    .enumerate()
+                    .map(|(i, candidate)| {
+                        let mut style = Style::default();
+                        if i == popup.selected {
+                            style = style.add_modifier(Modifier::REVERSED | Modifier::BOLD);
+                        }
+                        Line::from(Span::styled(candidate.as_str(), style))
+                    })
+                    .collect();
+                draw_popup(frame, anchor, lines, "<Enter>");
+            }
+            frame.set_cursor_position(cursor);
+        } else if let Some(input) = self
+            .quick_add
+            .clone()
+            .or_else(|| self.filter_input.clone())
+            .or_else(|| self.jump.clone().filter(|_| self.view == View::Week))
+        {
+            let area = frame.area();
+            frame.set_cursor_position(Position::new(
+                (area.x + 1 + input.width() as u16).min(area.right().saturating_sub(2)),
+                area.bottom().saturating_sub(2),
+            ));
+        }
+        frame.render_widget(&self.toasts, frame.area());
+    }
+
+    fn draw_completions(
+        &self,
+        frame: &mut Frame,
+        cursor: Position,
+        word: &str,
+        candidates: &[String],
+        selected: usize,
+    ) {
+        let prefix = word.chars().next().unwrap_or(tag::TAG);
+        let lines: Vec<Line> = candidates
+            .iter()
+            .enumerate()
+            .map(|(i, name)| {
+                let marker = format!("{prefix}{name}");
+                let mut style = Style::default().fg(tag::color(&marker, &self.settings.colors));
+                if i == selected {
+                    style = style.add_modifier(Modifier::REVERSED | Modifier::BOLD);
+                }
+                Line::from(Span::styled(marker, style))
+            })
+            .collect();
+        let anchor = Position::new(cursor.x.saturating_sub(word.width() as u16), cursor.y);
+        draw_popup(frame, anchor, lines, "<Tab>");
+    }
+
+    /// Draws the command palette near the top, the matching actions below what was typed.
+    fn draw_palette(&self, frame: &mut Frame, palette: &Palette) {
+        let matches = palette.matches(self.read_only);
+        let screen = frame.area();
+        let height = (matches.len().max(1) as u16 + 4).min(screen.height.saturating_sub(2));
+        let [area] = Layout::horizontal([Constraint::Percentage(60)])
+            .flex(Flex::Center)
+            .areas(Rect::new(screen.x, screen.y + 1, screen.width, height));
+        let block = Block::bordered()
+            .title(Span::styled(
+                t("Commands"),
+                Style::default().bold().fg(COLOR_SECONDARY),
+            ))
+            .title_bottom(key_hints(
+                &[("Run", "<Enter>"), ("Close", "<ESC>")],
+                COLOR_PRIMARY,
+            ))
+            .title_style(Style::default().bold().fg(Color::White))
+            .border_style(Color::White);
+        let inner = block.inner(area);
+        frame.render_widget(Clear, area);
+        frame.render_widget(block, area);
+        let [input_area, list_area] =
+            Layout::vertical([Constraint::Length(2), Constraint::Fill(1)]).areas(inner);
+        frame.render_widget(
+            Paragraph::new(format!("> {}", palette.input)).style(Style::default().bold()),
+            input_area,
+        );
+        frame.set_cursor_position(Position::new(
+            (input_area.x + 2 + palette.input.width() as u16)
+                .min(input_area.right().saturating_sub(1)),
+            input_area.y,
+        ));
+        if matches.is_empty() {
+            frame.render_widget(
+                Paragraph::new(t("No matching command")).style(COLOR_TERTIARY_DARK),
+                list_area,
+            );
+            return;
+        }
+        // keeps the selected action in sight on a small screen
+        let offset = palette
+            .selected
+            .saturating_sub(list_area.height.saturating_sub(1) as usize);
+        let rows = matches.iter().enumerate().skip(offset).map(|(i, binding)| {
+            let mut style = Style::default();
+            if i == palette.selected {
+                style = style.add_modifier(Modifier::REVERSED | Modifier::BOLD);
+            }
+            Row::new(vec![
+                Cell::from(t(binding.description)),
+                Cell::from(Span::styled(
+                    binding.label,
+                    Style::default().fg(COLOR_PRIMARY),
+                )),
+            ])
+            .style(style)
+        });
+        let keys = matches.iter().map(|b| b.label.width()).max().unwrap_or(0) as u16;
+        frame.render_widget(
+            Table::new(rows, [Constraint::Fill(1), Constraint::Length(keys)]),
+            list_area,
+        );
+    }
+
+    fn draw_review(&self, frame: &mut Frame, review: &review::Review) {
+        let Some(item) = self.logs.iter().find(|i| i.id() == review.id()) else {
+            return;
+        };
+        let title = format!(
+            " {} {}, {} · {}/{} ",
+            t("Review"),
+            i18n::weekday(chrono::Datelike::weekday(&review.day)),
+            i18n::long_date(review.day),
+            review.index + 1,
+            review.ids.len()
+        );
+        let hints: &[(&str, &str)] = if review.duration.is_some() {
+            &[("Add", "<Enter>"), ("Cancel", "<ESC>")]
+        } else {
+            &[
+                ("Next", "<j> | <Enter>"),
+                ("Previous", "<k>"),
+                ("Edit", "<e>"),
+                ("Done", "<x>"),
+                ("Time", "<t>"),
+                ("Finish", "<q> | <ESC>"),
+            ]
+        };
+        let block = Block::bordered()
+            .title(Span::styled(
+                title,
+                Style::default().bold().fg(COLOR_SECONDARY),
+            ))
+            .title_bottom(key_hints(hints, COLOR_PRIMARY))
+            .title_style(Style::default().bold().fg(Color::White));
+
+        let mut status = vec![Span::styled(
+            item.created().format("%H:%M").to_string(),
+            Style::default().fg(COLOR_TERTIARY_DARK),
+        )];
+        if item.tracked() > chrono::TimeDelta::zero() {
+            status.push(Span::raw(format!("  ⏱ {}", focus::format(item.tracked()))));
+        }
+        if item.done() {
+            status.push(Span::styled(
+                format!("  ✅ {}", t("Done")),
+                Style::default().fg(COLOR_PRIMARY).bold(),
+            ));
+        }
+        let mut lines = vec![Line::from(status), Line::from("")];
+        lines.extend(
+            item.content()
+                .lines()
+                .map(|line| Line::from(line.to_owned())),
+        );
+
+        let area = popup_area(frame.area(), 70, 60);
+        frame.render_widget(Clear, area);
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+        let [content_area, input_area] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(inner);
+        frame.render_widget(
+            Paragraph::new(lines).wrap(ratatui::widgets::Wrap { trim: false }),
+            content_area,
+        );
+        if let Some(ref input) = review.duration {
+            let label = format!("{} ", t("Add time, e.g. 1h30m:"));
+            frame.set_cursor_position(Position::new(
+                (input_area.x + (label.width() + input.width()) as u16)
+                    .min(input_area.right().saturating_sub(1)),
+                input_area.y,
+            ));
+            let mut line = Line::from(vec![
+                Span::styled(label, Style::default().fg(COLOR_SECONDARY).bold()),
+                Span::raw(input.as_str()),
+            ]);
+            if let Some(ref error) = review.error {
+                line.push_span(Span::styled(
+                    format!("  {error}"),
+                    Style::default().fg(Color::LightRed).bold(),
+                ));
+            }
+            frame.render_widget(Paragraph::new(line), input_area);
+        }
+    }
+
+    fn draw_restore(&self, frame: &mut Frame, item: &Item) {
+        let block = Block::bordered()
+            .title(Span::styled(
+                t("Unsaved Draft"),
+                Style::default().bold().fg(COLOR_SECONDARY),
+            ))
+            .title_bottom(Line::from(vec![
+                Span::raw(format!(" {} ", t("Restore"))),
+                Span::styled(
+                    "<y> | <Enter>",
+                    Style::default()
+                        .fg(COLOR_PRIMARY)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(format!(" {} ", t("Discard"))),
+                Span::styled(
+                    "<n> | <ESC>",
+                    Style::default()
+                        .fg(COLOR_PRIMARY)
+                        .add_modifier(Modifier::BOLD),
+                ),
+            ]))
+            .title_style(Style::default().bold().fg(Color::White));
+
+        let area = popup_area(frame.area(), 60, 40);
+        frame.render_widget(Clear, area);
+        frame.render_widget(
+            Paragraph::new(vec![
+                Line::from(Span::styled(
+                    i18n::fill(
+                        "An edit from {} was not saved. Restore it?",
+                        &[&item.modified().format("%Y-%m-%d %H:%M:%S").to_string()],
+                    ),
+                    Style::default().fg(COLOR_SECONDARY).bold(),
+                )),
+                Line::from(""),
+                Line::from(item.content()),
+            ])
+            .block(block)
+            .wrap(ratatui::widgets::Wrap { trim: false }),
+            area,
+        );
+    }
+
+    fn draw_whats_new(&self, frame: &mut Frame, version: &str) {
+        let block = Block::bordered()
+            .title(Span::styled(
+                i18n::fill("What's new in {}", &[changelog::VERSION]),
+                Style::default().bold().fg(COLOR_SECONDARY),
+            ))
+            .title_bottom(key_hints(
+                &[("Scroll", "<j> | <k>"), ("Close", "<Enter> | <q> | <ESC>")],
+                COLOR_PRIMARY,
+            ))
+            .title_style(Style::default().bold().fg(Color::White));
+
+        let mut lines = Vec::new();
+        for (version, notes) in changelog::since(version) {
+            lines.push(Line::from(Span::styled(
+                version,
+                Style::default().fg(COLOR_PRIMARY).bold(),
+            )));
+            lines.extend(notes.lines().map(Line::from));
+            lines.push(Line::from(""));
+        }
+        let migrations = changelog::migrations_since(version);
+        if !migrations.is_empty() {
+            lines.push(Line::from(Span::styled(
+                t("Your data file was updated"),
+                Style::default().fg(COLOR_PRIMARY).bold(),
+            )));
+            lines.extend(migrations.into_iter().map(|m| Line::from(format!("- {m}"))));
+        }
+
+        let area = popup_area(frame.area(), 80, 80);
+        frame.render_widget(Clear, area);
+        frame.render_widget(
+            Paragraph::new(lines)
+                .block(block)
+                .wrap(ratatui::widgets::Wrap { trim: false })
+                .scroll((self.whats_new_scroll, 0)),
+            area,
+        );
+    }
+
+    pub fn handle_whats_new_keys(&mut self, key_event: KeyEvent) -> Result<()> {
+        match key_event.code {
+            KeyCode::Char('j') | KeyCode::Down => {
+                self.whats_new_scroll = self.whats_new_scroll.saturating_add(1);
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.whats_new_scroll = self.whats_new_scroll.saturating_sub(1);
+            }
+            KeyCode::Enter | KeyCode::Char('q') | KeyCode::Char(' ') | KeyCode::Esc => {
+                self.whats_new = None;
+                self.version = Some(changelog::VERSION.to_owned());
+                self.save()?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn draw_confirm_quit(&self, frame: &mut Frame) {
+        if !self.dirty {
+            let block = Block::bordered()
+                .title(Span::styled(
+                    t("Quit"),
+                    Style::default().bold().fg(COLOR_SECONDARY),
+                ))
+                .title_bottom(key_hints(
+                    &[("Quit", "<y> | <Enter> | <q>"), ("Cancel", "<n> | <ESC>")],
+                    COLOR_PRIMARY,
+                ))
+                .title_style(Style::default().bold().fg(Color::White));
+            let area = popup_area(frame.area(), 50, 20);
+            frame.render_widget(Clear, area);
+            frame.render_widget(
+                Paragraph::new(Line::from(Span::styled(
+                    t("Quit lw?"),
+                    Style::default().fg(COLOR_SECONDARY).bold(),
+                )))
+                .block(block),
+                area,
+            );
+            return;
+        }
+        let block = Block::bordered()
+            .title(Span::styled(
+                t("Unsaved Changes"),
+                Style::default().bold().fg(COLOR_SECONDARY),
+            ))
+            .title_bottom(key_hints(
+                &[
+                    ("Save and quit", "<y> | <Enter>"),
+                    ("Quit", "<n>"),
+                    ("Cancel", "<ESC>"),
+                ],
+                COLOR_PRIMARY,
+            ))
+            .title_style(Style::default().bold().fg(Color::White));
+
+        let area = popup_area(frame.area(), 50, 20);
+        frame.render_widget(Clear, area);
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(
+                t("The log has changes that were not saved. Save them before quitting?"),
+                Style::default().fg(COLOR_SECONDARY).bold(),
+            )))
+            .block(block)
+            .wrap(ratatui::widgets::Wrap { trim: false }),
+            area,
+        );
+    }
+
+    fn draw_duplicate(&self, frame: &mut Frame, duplicate: &Item, item: &Item) {
+        let block = Block::bordered()
+            .title(Span::styled(
+                t("Duplicate Entry"),
+                Style::default().bold().fg(COLOR_SECONDARY),
+            ))
+            .title_bottom(key_hints(
+                &[("Add anyway", "<y> | <Enter>"), ("Discard", "<n> | <ESC>")],
+                COLOR_PRIMARY,
+            ))
+            .title_style(Style::default().bold().fg(Color::White));
+
+        let area = popup_area(frame.area(), 60, 40);
+        frame.render_widget(Clear, area);
+        frame.render_widget(
+            Paragraph::new(vec![
+                Line::from(Span::styled(
+                    i18n::fill(
+                        "The same entry was added at {}. Add it again?",
+                        &[&duplicate.created().format("%H:%M:%S").to_string()],
+                    ),
+                    Style::default().fg(COLOR_SECONDARY).bold(),
+                )),
+                Line::from(""),
+                Line::from(item.content()),
+            ])
+            .block(block)
+            .wrap(ratatui::widgets::Wrap { trim: false }),
+            area,
+        );
+    }
+
+    fn draw_save_error(&self, frame: &mut Frame, error: &str) {
+        let block = Block::bordered()
+            .title(Span::styled(
+                t("Saving Failed"),
+                Style::default().bold().fg(Color::LightRed),
+            ))
+            .title_bottom(key_hints(
+                &[("Retry", "<r> | <Enter>"), ("Later", "<ESC>")],
+                COLOR_PRIMARY,
+            ))
+            .title_style(Style::default().bold().fg(Color::White));
+
+        let area = popup_area(frame.area(), 60, 30);
+        frame.render_widget(Clear, area);
+        frame.render_widget(
+            Paragraph::new(vec![
+                Line::from(Span::styled(
+                    t("The changes are kept until they can be saved."),
+                    Style::default().fg(COLOR_SECONDARY).bold(),
+                )),
+                Line::from(""),
+                Line::from(error.to_owned()),
+            ])
+            .block(block)
+            .wrap(ratatui::widgets::Wrap { trim: false }),
+            area,
+        );
+    }
+
+    pub fn handle_save_error_keys(&mut self, key_event: KeyEvent) -> Result<()> {
+        match key_event.code {
+            KeyCode::Char('r') | KeyCode::Enter => {
+                self.save()?;
+                self.toasts.info(t("Saved"));
+            }
+            KeyCode::Esc => self.save_error = None,
+            _ => {}
+        }
+        Ok(())
+    }
+
+    pub fn handle_duplicate_keys(&mut self, key_event: KeyEvent) -> Result<()> {
+        match key_event.code {
+            KeyCode::Char('y') | KeyCode::Enter => {
+                if let Some((_, item)) = self.duplicate.take() {
+                    self.add(item);
+                    self.state.select_first();
+                    self.save()?;
+                }
+            }
+            KeyCode::Char('n') | KeyCode::Esc => self.duplicate = None,
+            _ => {}
+        }
+        Ok(())
+    }
+
+    pub fn handle_confirm_quit_keys(&mut self, key_event: KeyEvent) -> Result<()> {
+        if !self.dirty {
+            match key_event.code {
+                KeyCode::Char('y' | 'q') | KeyCode::Enter => self.exit = true,
+                KeyCode::Char('n') | KeyCode::Esc => {}
+                _ => return Ok(()),
+            }
+            self.confirm_quit = false;
+            return Ok(());
+        }
+        match key_event.code {
+            KeyCode::Char('y') | KeyCode::Enter => {
+                self.save()?;
+                self.exit = true;
+            }
+            KeyCode::Char('n') => self.exit = true,
+            KeyCode::Esc | KeyCode::Char('q') => {}
+            _ => return Ok(()),
+        }
+        self.confirm_quit = false;
+        Ok(())
+    }
+
+    pub fn handle_restore_keys(&mut self, key_event: KeyEvent) -> Result<()> {
+        match key_event.code {
+            KeyCode::Char('y') | KeyCode::Enter => {
+                if let Some(item) = self.restore.take() {
+                    self.open_editor(item);
+                }
+            }
+            KeyCode::Char('n') | KeyCode::Esc => {
+                self.restore = None;
+                draft::discard()?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Persists the currently edited item as a draft, or removes the draft once editing ended.
+    fn sync_draft(&mut self) -> Result<()> {
+        match self.edit {
+            Some(ref item) if self.draft.as_ref() != Some(item) => {
+                draft::save(item)?;
+                self.draft = Some(item.clone());
+            }
+            None if self.draft.is_some() => {
+                draft::discard()?;
+                self.draft = None;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn open_editor(&mut self, item: Item) {
+        self.cursor = item.content().len();
+        self.completion = None;
+        #[cfg(feature = "spellcheck")]
+        {
+            self.spelling.popup = None;
+        }
+        self.link_focus = None;
+        self.field_input = None;
+        self.edit = Some(item);
+    }
+
+    /// Entries linked to `item` in either direction, with a label describing the relation.
+    fn linked(&self, item: &Item) -> Vec<(&'static str, &Item)> {
+        let links = item.links();
+        self.logs
+            .iter()
+            .filter(|i| links.contains(&i.id()))
+            .map(|i| ("follow-up of", i))
+            .chain(
+                self.logs
+                    .iter()
+                    .filter(|i| i.links_to(item))
+                    .map(|i| ("followed up by", i)),
+            )
+            .collect()
+    }
+
+    /// Typing into the value of the custom field focused in the editor.
+    fn handle_field_keys(&mut self, key_event: KeyEvent, item: &Item) -> bool {
+        let Some(ref mut input) = self.field_input else {
+            return false;
+        };
+        match key_event.code {
+            KeyCode::Char(c) if !key_event.modifiers.contains(KeyModifiers::CONTROL) => {
+                input.push(c)
+            }
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Esc => self.field_input = None,
+            KeyCode::Enter => {
+                let input = input.trim().to_owned();
+                let offset = self.linked(item).len() + item.attachments().len();
+                let fields = self.fields(item);
+                let Some((name, kind)) = self
+                    .link_focus
+                    .and_then(|focus| fields.get(focus.checked_sub(offset)?))
+                else {
+                    self.field_input = None;
+                    return true;
+                };
+                // clearing the input removes the field
+                let value = if input.is_empty() {
+                    Ok(String::new())
+                } else {
+                    kind.parse(&input)
+                };
+                match value {
+                    Ok(value) => {
+                        let mut item = item.clone();
+                        item.set_field(name, value);
+                        self.edit = Some(item);
+                        self.field_input = None;
+                    }
+                    Err(e) => self.toasts.error(i18n::fill(
+                        "invalid value for {}: {}",
+                        &[name, &e.to_string()],
+                    )),
+                }
+            }
+            _ => {}
+        }
+        true
+    }
+
+    /// Moves between and opens the linked entries, attachments and fields listed in the
+    /// editor.
+    fn handle_link_keys(&mut self, key_event: KeyEvent, item: &Item) -> Result<bool> {
+        let linked = self.linked(item);
+        let attachments = linked.len() + item.attachments().len();
+        let fields = self.fields(item);
+        let count = attachments + fields.len();
+        if count == 0 {
+            self.link_focus = None;
+            return Ok(false);
+        }
+        let Some(selected) = self.link_focus else {
+            if key_event.code == KeyCode::Tab && self.completion.is_none() {
+                self.link_focus = Some(0);
+                return Ok(true);
+            }
+            return Ok(false);
+        };
+        let selected = selected.min(count - 1);
+        match key_event.code {
+            KeyCode::Down | KeyCode::Tab => self.link_focus = Some((selected + 1) % count),
+            KeyCode::Up | KeyCode::BackTab => {
+                self.link_focus = Some((selected + count - 1) % count)
+            }
+            KeyCode::Enter if selected >= attachments => {
+                let (name, kind) = &fields[selected - attachments];
+                if *kind == FieldType::Bool {
+                    let value = if item.field(name) == Some("true") {
+                        "false"
+                    } else {
+                        "true"
+                    };
+                    let mut item = item.clone();
+                    item.set_field(name, value.to_owned());
+                    self.edit = Some(item);
+                } else {
+                    self.field_input = Some(item.field(name).unwrap_or_default().to_owned());
+                }
+            }
+            KeyCode::Enter if selected >= linked.len() => {
+                let path = item.attachments()[selected - linked.len()].path(&item.id());
+                if let Err(e) = attachment::open(&path) {
+                    self.toasts.error(e.to_string());
+                }
+            }
+            KeyCode::Enter => {
+                let target = linked[selected].1.clone();
+                let stored = self.logs.iter().find(|i| i.id() == item.id());
+                if stored != Some(item) && !item.content().trim().is_empty() {
+                    self.store(item.clone());
+                    self.save()?;
+                }
+                self.select(&target.id());
+                self.open_editor(target);
+            }
+            KeyCode::Esc => self.link_focus = None,
+            _ => {
+                self.link_focus = None;
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    /// Tags or projects completing the marker in front of the cursor, along with the start
+    /// of that marker.
+    fn completions(&self, item: &Item) -> Option<(usize, Vec<String>)> {
+        let content = item.content();
+        let cursor = self.cursor.min(content.len());
+        let start = editor::word_start(&content, cursor);
+        let word = &content[start..cursor];
+        let prefix = word
+            .chars()
+            .next()
+            .filter(|c| matches!(*c, tag::TAG | tag::PROJECT))?;
+        let mut recent: Vec<&Item> = self.logs.iter().filter(|i| i.id() != item.id()).collect();
+        recent.sort_by_key(|i| std::cmp::Reverse(i.modified()));
+        let candidates = tag::complete(
+            recent.iter().map(|i| i.content()),
+            prefix,
+            &word[prefix.len_utf8()..],
+        );
+        (!candidates.is_empty()).then_some((start, candidates))
+    }
+
+    fn handle_completion_keys(&mut self, key_event: KeyEvent, item: &Item) -> bool {
+        let Some(selected) = self.completion else {
+            return false;
+        };
+        let Some((start, candidates)) = self.completions(item) else {
+            return false;
+        };
+        match key_event.code {
+            KeyCode::Down => self.completion = Some((selected + 1) % candidates.len()),
+            KeyCode::Up => {
+                self.completion = Some((selected + candidates.len() - 1) % candidates.len())
+            }
+            KeyCode::Tab => {
+                let candidate = &candidates[selected.min(candidates.len() - 1)];
+                self.apply_edit(item.clone(), |s, c| {
+                    let prefix = s[start..].chars().next().unwrap_or(tag::TAG);
+                    let mut replacement = format!("{prefix}{candidate}");
+                    if !s[c..].starts_with(char::is_whitespace) {
+                        replacement.push(' ');
+                    }
+                    s.replace_range(start..c, &replacement);
+                    start + replacement.len()
+                });
+                self.completion = None;
+            }
+            KeyCode::Esc => self.completion = None,
+            _ => return false,
+        }
+        true
+    }
+
+    /// Offers corrections for the misspelled word at the cursor on `<CTRL-.>` or `<F7>`, which
+    /// terminals without the kitty keyboard protocol send for it.
+    #[cfg(feature = "spellcheck")]
+    fn handle_spelling_keys(&mut self, key_event: KeyEvent, item: &Item) -> bool {
+        let Some(mut popup) = self.spelling.popup.take() else {
+            let ctrl = key_event.modifiers.contains(KeyModifiers::CONTROL);
+            if !matches!(key_event.code, KeyCode::Char('.') if ctrl)
+                && key_event.code != KeyCode::F(7)
+            {
+                return false;
+            }
+            let command = self
+                .settings
+                .spellcheck
+                .as_deref()
+                .unwrap_or(spell::DEFAULT_COMMAND);
+            match self.spelling.suggest(command, &item.content(), self.cursor) {
+                Ok(Some(popup)) if popup.candidates.is_empty() => {
+                    self.toasts.info(t("No suggestions"));
+                }
+                Ok(Some(popup)) => self.spelling.popup = Some(popup),
+                Ok(None) => self.toasts.info(t("No misspelled word at the cursor")),
+                Err(e) => self.toasts.error(e.to_string()),
+            }
+            return true;
+        };
+        let count = popup.candidates.len();
+        match key_event.code {
+            KeyCode::Down | KeyCode::Tab => popup.selected = (popup.selected + 1) % count,
+            KeyCode::Up | KeyCode::BackTab => popup.selected = (popup.selected + count - 1) % count,
+            KeyCode::Enter => {
+                let candidate = &popup.candidates[popup.selected];
+                self.apply_edit(item.clone(), |s, _| {
+                    s.replace_range(popup.word.clone(), candidate);
+                    popup.word.start + candidate.len()
+                });
+                return true;
+            }
+            KeyCode::Esc => return true,
+            _ => return false,
+        }
+        self.spelling.popup = Some(popup);
+        true
+    }
+
+    fn apply_edit<F: FnOnce(&mut String, usize) -> usize>(&mut self, mut item: Item, f: F) {
+        let mut content = item.content();
+        let cursor = self.cursor.min(content.len());
+        let cursor = f(&mut content, cursor);
+        if content != item.content() {
+            item.update(content);
+        }
+        self.cursor = cursor;
+        self.edit = Some(item);
+    }
+
+    pub fn handle_edit_keys(&mut self, key_event: KeyEvent, item: Item) -> Result<()> {
+        #[cfg(feature = "spellcheck")]
+        if self.handle_spelling_keys(key_event, &item) {
+            return Ok(());
+        }
+        if self.handle_field_keys(key_event, &item)
+            || self.handle_completion_keys(key_event, &item)
+            || self.handle_link_keys(key_event, &item)?
+        {
+            return Ok(());
+        }
+        let ctrl = key_event.modifiers.contains(KeyModifiers::CONTROL);
+        let alt = key_event.modifiers.contains(KeyModifiers::ALT);
+        match key_event.code {
+            KeyCode::Up if alt => self.resize_editor(self.popup.resize(0, 1)),
+            KeyCode::Down if alt => self.resize_editor(self.popup.resize(0, -1)),
+            KeyCode::Right if alt => self.resize_editor(self.popup.resize(1, 0)),
+            KeyCode::Left if alt => self.resize_editor(self.popup.resize(-1, 0)),
+            KeyCode::Char('0') if alt => self.resize_editor(popup::Size::default()),
+            KeyCode::Backspace if ctrl => self.apply_edit(item, editor::delete_word),
+            KeyCode::Backspace => self.apply_edit(item, editor::backspace),
+            KeyCode::Delete => self.apply_edit(item, editor::delete),
+            KeyCode::Left => self.apply_edit(item, |s, c| editor::prev_boundary(s, c)),
+            KeyCode::Right => self.apply_edit(item, |s, c| editor::next_boundary(s, c)),
+            KeyCode::Up => self.apply_edit(item, |s, c| editor::line_up(s, c)),
+            KeyCode::Down => self.apply_edit(item, |s, c| editor::line_down(s, c)),
+            KeyCode::Home => self.apply_edit(item, |s, c| editor::line_start(s, c)),
+            KeyCode::End => self.apply_edit(item, |s, c| editor::line_end(s, c)),
+            KeyCode::Esc => {
+                self.edit = None;
+            }
+            KeyCode::Char('o') | KeyCode::Enter if ctrl => {
+                let content = item.content();
+                if !content.replace("\n", "").replace("\t", "").is_empty() {
+                    let mut chunks = self.split_content(&content).into_iter();
+                    let mut item = item;
+                    if let Some(first) = chunks.next() {
+                        item.update(first);
+                    }
+                    self.store(item);
+                    let count = 1 + chunks.len();
+                    chunks.for_each(|c| self.add(c.into()));
+                    self.edit = None;
+                    self.save()?;
+                    self.toasts.info(if count > 1 {
+                        i18n::fill("Saved as {} entries", &[&count.to_string()])
+                    } else {
+                        t("Saved").to_owned()
+                    });
+                }
+            }
+            KeyCode::Char('s') if ctrl => {
+                let content = item.content();
+                let cursor = self.cursor.min(content.len());
+                let (head, tail) = content.split_at(cursor);
+                if !head.trim().is_empty() && !tail.trim().is_empty() {
+                    let mut item = item;
+                    item.update(head.trim_end().to_owned());
+                    self.store(item);
+                    let rest = Item::from(tag::inherit(head, tail.trim_start()));
+                    self.add(rest.clone());
+                    self.open_editor(rest);
+                    self.save()?;
+                }
+            }
+            KeyCode::Char('t') if ctrl => {
+                let stamp = note_prefix();
+                self.apply_edit(item, |s, c| {
+                    // a note typed at the start of a line doesn't need a line of its own
+                    let stamp = if s[..c].is_empty() || s[..c].ends_with('\n') {
+                        &stamp[1..]
+                    } else {
+                        &stamp
+                    };
+                    editor::insert(s, c, stamp)
+                });
+            }
+            KeyCode::Enter => self.apply_edit(item, |s, c| editor::insert(s, c, "\n")),
+            KeyCode::Char('h') if ctrl => self.apply_edit(item, editor::delete_word),
+            KeyCode::Char('c') if ctrl => {
+                self.edit = None;
+            }
+            KeyCode::Char(key) => self.apply_edit(item, |s, c| {
+                editor::insert(s, c, key.encode_utf8(&mut [0; 4]))
+            }),
+            _ => {}
+        }
+        self.completion = match (key_event.code, &self.edit) {
+            (KeyCode::Char(_) | KeyCode::Backspace | KeyCode::Delete, Some(item)) if !ctrl => {
+                self.completions(item).map(|_| 0)
+            }
+            _ => None,
+        };
+        Ok(())
+    }
+
+    fn resize_editor(&mut self, size: popup::Size) {
+        self.popup = size;
+        if !self.read_only
+            && !self.in_memory
+            && let Err(e) = popup::save(size)
+        {
+            self.toasts.error(e.to_string());
+        }
+    }
+
+    pub fn handle_quick_add_keys(&mut self, key_event: KeyEvent, mut input: String) -> Result<()> {
+        match key_event.code {
+            KeyCode::Esc => self.quick_add = None,
+            KeyCode::Char('c') if key_event.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.quick_add = None;
+            }
+            KeyCode::Enter => {
+                self.quick_add = None;
+                if !input.trim().is_empty() {
+                    match self.add_unique(input.trim().into()) {
+                        Added::Confirm { duplicate, item } => {
+                            self.duplicate = Some((duplicate, item));
+                        }
+                        Added::Skipped(_) => {
+                            self.toasts
+                                .info(t("Skipped, the same entry was added just before"));
+                        }
+                        Added::New => {
+                            self.state.select_first();
+                            self.save()?;
+                            self.toasts.info(t("Added"));
+                        }
+                        Added::Merged(_) => {
+                            self.state.select_first();
+                            self.save()?;
+                            self.toasts
+                                .info(t("Merged into the same entry added just before"));
+                        }
+                    }
+                }
+            }
+            KeyCode::Backspace => {
+                if key_event.modifiers.contains(KeyModifiers::CONTROL) {
+                    input = editor::remove_last_word(&input).to_owned();
+                } else {
+                    editor::pop_grapheme(&mut input);
+                }
+                self.quick_add = Some(input);
+            }
+            KeyCode::Char('h') if key_event.modifiers.contains(KeyModifiers::CONTROL) => {
+                input = editor::remove_last_word(&input).to_owned();
+                self.quick_add = Some(input);
+            }
+            KeyCode::Char(key) => {
+                input.push(key);
+                self.quick_add = Some(input);
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn switch_view(&mut self, view: View) {
+        if view != self.view {
+            self.previous_view = self.view;
+            self.view = view;
+        }
+        self.delete = None;
+    }
+
+    fn open_search(&mut self, prefix: &str) {
+        let mut input = self
+            .filter
+            .as_ref()
+            .map(Query::to_string)
+            .unwrap_or_default();
+        if !input.is_empty() && !prefix.is_empty() {
+            input.push(' ');
+        }
+        input.push_str(prefix);
+        self.previous_filter = self.filter.clone();
+        self.filter_error = None;
+        self.filter_input = Some(input);
+        self.delete = None;
+    }
+
+    fn cancel_search(&mut self) {
+        let previous = self.previous_filter.take();
+        self.filter_input = None;
+        self.filter_error = None;
+        self.set_filter(previous);
+    }
+
+    pub fn handle_filter_keys(&mut self, key_event: KeyEvent, mut input: String) {
+        match key_event.code {
+            KeyCode::Esc => {
+                self.cancel_search();
+                return;
+            }
+            KeyCode::Char('c') if key_event.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.cancel_search();
+                return;
+            }
+            KeyCode::Enter => {
+                if self.filter_error.is_none() {
+                    self.filter_input = None;
+                    self.previous_filter = None;
+                }
+                return;
+            }
+            KeyCode::Backspace => {
+                if key_event.modifiers.contains(KeyModifiers::CONTROL) {
+                    input = editor::remove_last_word(&input).to_owned();
+                } else {
+                    editor::pop_grapheme(&mut input);
+                }
+            }
+            KeyCode::Char('h') if key_event.modifiers.contains(KeyModifiers::CONTROL) => {
+                input = editor::remove_last_word(&input).to_owned();
+            }
+            KeyCode::Char(key) => input.push(key),
+            _ => return,
+        }
+        self.update_filter(input);
+    }
+
+    fn update_filter(&mut self, input: String) {
+        // the table follows the search while typing, as long as the query is valid
+        match input.parse::<Query>() {
+            Ok(query) => {
+                self.filter_error = None;
+                self.set_filter((!query.is_empty()).then_some(query));
+            }
+            Err(e) => self.filter_error = e.to_string().lines().next().map(str::to_owned),
+        }
+        self.filter_input = Some(input);
+    }
+
+    /// Shows the week of `cursor`, loading the months it spans.
+    fn show_week(&mut self, cursor: week::Cursor) -> Result<()> {
+        for day in [cursor.start, cursor.end()] {
+            let items = self.shards.load(&shard::month_of(day))?;
+            self.extend(items);
+        }
+        let entries = week::days(&self.logs, cursor.start)[cursor.day].len();
+        self.week = week::Cursor {
+            row: cursor.row.min(entries.saturating_sub(1)),
+            ..cursor
+        };
+        Ok(())
+    }
+
+    pub fn handle_week_keys(&mut self, key_event: KeyEvent) -> Result<()> {
+        if let Some(mut input) = self.jump.take() {
+            let ctrl = key_event.modifiers.contains(KeyModifiers::CONTROL);
+            match key_event.code {
+                KeyCode::Esc => {}
+                KeyCode::Char('c') if ctrl => {}
+                KeyCode::Enter => match dates::day(&input, chrono::Local::now().date_naive()) {
+                    Ok(day) => {
+                        self.jump_error = None;
+                        return self.show_week(week::Cursor::on(day));
+                    }
+                    Err(e) => {
+                        self.jump_error = e.to_string().lines().next().map(str::to_owned);
+                        self.jump = Some(input);
+                    }
+                },
+                KeyCode::Backspace => {
+                    input.pop();
+                    self.jump = Some(input);
+                }
+                KeyCode::Char(c) if !ctrl => {
+                    input.push(c);
+                    self.jump = Some(input);
+                }
+                _ => self.jump = Some(input),
+            }
+            if self.jump.is_none() {
+                self.jump_error = None;
+            }
+            return Ok(());
+        }
+        let mut cursor = self.week;
+        match key_event.code {
+            KeyCode::Char('w') | KeyCode::Char('q') | KeyCode::Esc => {
+                self.switch_view(View::Log);
+                return Ok(());
+            }
+            KeyCode::Char('h') | KeyCode::Left => cursor.shift(-1),
+            KeyCode::Char('l') | KeyCode::Right => cursor.shift(1),
+            KeyCode::Char('H') => cursor.shift(-7),
+            KeyCode::Char('L') => cursor.shift(7),
+            KeyCode::Char('t') => cursor = week::Cursor::default(),
+            KeyCode::Char('g') => {
+                self.jump = Some(String::new());
+                return Ok(());
+            }
+            KeyCode::Char('j') | KeyCode::Down => cursor.row += 1,
+            KeyCode::Char('k') | KeyCode::Up => cursor.row = cursor.row.saturating_sub(1),
+            KeyCode::Enter | KeyCode::Char('e') | KeyCode::Char(' ') if !self.read_only => {
+                let days = week::days(&self.logs, cursor.start);
+                if let Some(item) = days[cursor.day].get(cursor.row) {
+                    let item = (*item).clone();
+                    self.open_editor(item);
+                }
+                return Ok(());
+            }
+            _ => return Ok(()),
+        }
+        if cursor.day != self.week.day || cursor.start != self.week.start {
+            cursor.row = 0;
+        }
+        self.show_week(cursor)
+    }
+
+    pub fn handle_stats_keys(&mut self, key_event: KeyEvent) {
+        match key_event.code {
+            KeyCode::Char('s') | KeyCode::Char('q') | KeyCode::Esc => self.switch_view(View::Log),
+            KeyCode::Char('S') => self.project_sort = self.project_sort.toggle(),
+            _ => {}
+        }
+    }
+
+    pub fn handle_main_keys(&mut self, key_event: KeyEvent) -> Result<()> {
+        let escape_pending = std::mem::take(&mut self.escape_pending);
+        if key_event.code == KeyCode::Esc && self.filter.is_none() {
+            if !self.settings.quit_on_esc.unwrap_or(true) {
+                return Ok(());
+            }
+            if self.dirty && !escape_pending {
+                self.escape_pending = true;
+                self.toasts
+                    .info(t("Unsaved changes, press <ESC> again or <q> to quit"));
+                return Ok(());
+            }
+        }
+        match action::find(key_event, self.filter.is_some()) {
+            Some(action) => self.perform(action),
+            None => Ok(()),
+        }
+    }
+
+    /// Runs `action` on the selected entry or the main view, unless it would change the log
+    /// in safe mode.
+    pub fn perform(&mut self, action: Action) -> Result<()> {
+        if self.read_only && action.writes() {
+            return Ok(());
+        }
+        match action {
+            Action::ClearSearch => self.set_filter(None),
+            Action::Quit if self.dirty || self.settings.confirm_quit => self.confirm_quit = true,
+            Action::Quit => self.exit = true,
+            Action::Save => {
+                self.save()?;
+                self.toasts.info(t("Saved"));
+            }
+            Action::Open => match self.selected().cloned() {
+                Some(item) => self.open_editor(item),
+                None => self.open_editor(Item::new()),
+            },
+            Action::Down => {
+                // scrolling past the oldest loaded entry fetches the previous month
+                let count = self.visible().len();
+                if self.period.is_none() && self.state.selected().is_some_and(|s| s + 1 >= count) {
+                    while self.visible().len() == count && self.load_older()? {}
+                }
+                self.state.select_next();
+                self.delete = None;
+            }
+            Action::Up => {
+                self.state.select_previous();
+                self.delete = None;
+            }
+            Action::Top => {
+                self.state.select_first();
+                self.delete = None;
+            }
+            Action::Bottom => {
+                self.load_all()?;
+                self.state.select_last();
+                self.delete = None;
+            }
+            Action::NewEntry => {
+                self.open_editor(Item::new());
+                self.delete = None;
+            }
+            Action::QuickAdd => {
+                self.quick_add = Some(String::new());
+                self.delete = None;
+            }
+            Action::OpenToday => {
+                self.open_today();
+                self.delete = None;
+            }
+            Action::Review => {
+                self.delete = None;
+                self.start_review(chrono::Local::now().date_naive());
+            }
+            Action::FollowUp => {
+                self.delete = None;
+                if let Some(selected) = self.selected().cloned() {
+                    let mut item = Item::from(tag::inherit(&selected.content(), ""));
+                    item.link(&selected);
+                    self.open_editor(item);
+                    self.cursor = 0;
+                }
+            }
+            Action::Statistics => {
+                self.load_all()?;
+                self.switch_view(View::Stats);
+            }
+            Action::Week => {
+                let day = self.selected().map(|item| item.created().date_naive());
+                self.show_week(day.map_or_else(week::Cursor::default, week::Cursor::on))?;
+                self.switch_view(View::Week);
+            }
+            Action::ExportWeek => self.export_week()?,
+            Action::PreviousView => self.switch_view(self.previous_view),
+            Action::Search => {
+                self.load_all()?;
+                self.open_search("");
+            }
+            Action::FilterPerson => {
+                self.load_all()?;
+                self.open_search("@");
+            }
+            Action::ToggleCurrent => {
+                self.delete = None;
+                if let Some(id) = self.selected().map(Item::id) {
+                    self.toggle_current(id);
+                    self.save()?;
+                    self.toasts.info(t(if self.current.is_some() {
+                        "Started working on the entry"
+                    } else {
+                        "Stopped working on the entry"
+                    }));
+                }
+            }
+            Action::CopyShortId | Action::CopyId => {
+                self.delete = None;
+                if let Some(item) = self.selected() {
+                    let id = match action {
+                        Action::CopyShortId => self.short_id(item),
+                        _ => item.id(),
+                    };
+                    clipboard::copy(&id)?;
+                    self.toasts.info(i18n::fill("copied {}", &[&id]));
+                }
+            }
+            Action::TogglePin => {
+                self.delete = None;
+                if let Some(item) = self.selected() {
+                    let pinned = !item.pinned();
+                    self.toggle_pin(item.id());
+                    self.save()?;
+                    self.toasts
+                        .info(t(if pinned { "Pinned" } else { "Unpinned" }));
+                }
+            }
+            Action::ToggleBillable => {
+                self.delete = None;
+                if let Some(item) = self.selected() {
+                    let billable = !item.billable();
+                    self.toggle_billable(item.id());
+                    self.save()?;
+                    self.toasts.info(t(if billable {
+                        "Marked as billable"
+                    } else {
+                        "Marked as not billable"
+                    }));
+                }
+            }
+            Action::ToggleSort => self.toggle_sort(),
+            Action::CyclePeriod => {
+                let day = self.selected_day();
+                let period = match self.period {
+                    Some(period) => period.narrow(day),
+                    None => Some(browse::Period::containing(browse::Span::Month, day)),
+                };
+                self.show_period(period)?;
+            }
+            Action::PreviousPeriod | Action::NextPeriod => {
+                let steps = if action == Action::NextPeriod { 1 } else { -1 };
+                let period = match self.period {
+                    Some(period) => period.step(steps),
+                    // starts from the month of the selected entry
+                    None => browse::Period::containing(browse::Span::Month, self.selected_day()),
+                };
+                self.show_period(Some(period))?;
+            }
+            Action::CycleDensity => {
+                self.density = self.density.next();
+            }
+            Action::Palette => {
+                self.palette = Some(Palette::default());
+                self.delete = None;
+            }
+            Action::Undo => {
+                self.delete = None;
+                match self.undo()? {
+                    Some(_) => {
+                        self.save()?;
+                        self.toasts.info(t("Undone"));
+                    }
+                    None => self.toasts.info(t("Nothing to undo")),
+                }
+            }
+            Action::Delete => {
+                let curr = self.state.selected();
+                match curr {
+                    None => {
+                        self.delete = None;
+                    }
+                    Some(_) => {
+                        if self.delete == curr
+                            && let Some(item) = self.selected()
+                        {
+                            let (id, short) = (item.id(), self.short_id(item));
+                            self.delete = None;
+                            self.remove(id);
+                            self.save()?;
+                            self.toasts
+                                .info(i18n::fill("Deleted {}, <u> to undo", &[&short]));
+                        } else {
+                            self.delete = curr;
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes the report of this week to an HTML file in the current directory, limited to
+    /// the entries matching the search.
+    fn export_week(&mut self) -> Result<()> {
+        self.load_all()?;
+        let today = chrono::Local::now().date_naive();
+        let start = export::Period::Week.start(today).unwrap_or(today);
+        let path = PathBuf::from(format!("lw-week-{start}.html"));
+        let logs = export::select(&self.logs, self.filter.as_ref());
+        fs::write(&path, export::html(&logs, export::Period::Week))
+            .map_err(|e| eyre!("failed to write {}: {e}", path.display()))?;
+        self.toasts.info(i18n::fill(
+            "Exported the week to {}",
+            &[&path.display().to_string()],
+        ));
+        Ok(())
+    }
+
+    /// Handles the keys of the command palette: typing narrows the actions down, `<Enter>`
+    /// runs the selected one.
+    fn handle_palette_keys(&mut self, key_event: KeyEvent, mut palette: Palette) -> Result<()> {
+        let ctrl = key_event.modifiers.contains(KeyModifiers::CONTROL);
+        let matches = palette.matches(self.read_only);
+        match key_event.code {
+            KeyCode::Esc => self.palette = None,
+            KeyCode::Char('c') if ctrl => self.palette = None,
+            KeyCode::Enter => {
+                self.palette = None;
+                if let Some(action) = matches.get(palette.selected).map(|b| b.action) {
+                    return self.perform(action);
+                }
+            }
+            KeyCode::Down | KeyCode::Tab => {
+                palette.selected = (palette.selected + 1).min(matches.len().saturating_sub(1));
+                self.palette = Some(palette);
+            }
+            KeyCode::Char('n') if ctrl => {
+                palette.selected = (palette.selected + 1).min(matches.len().saturating_sub(1));
+                self.palette = Some(palette);
+            }
+            KeyCode::Up | KeyCode::BackTab => {
+                palette.selected = palette.selected.saturating_sub(1);
+                self.palette = Some(palette);
+            }
+            KeyCode::Char('p') if ctrl => {
+                palette.selected = palette.selected.saturating_sub(1);
+                self.palette = Some(palette);
+            }
+            KeyCode::Backspace => {
+                palette.input.pop();
+                palette.selected = 0;
+                self.palette = Some(palette);
+            }
+            KeyCode::Char(c) if !ctrl => {
+                palette.input.push(c);
+                palette.selected = 0;
+                self.palette = Some(palette);
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Starts the review of the entries created on `day`.
+    fn start_review(&mut self, day: chrono::NaiveDate) {
+        match review::Review::new(&self.logs, day) {
+            Some(review) => self.review = Some(review),
+            None => self.toasts.info(t("Nothing was logged today")),
+        }
+    }
+
+    /// Handles the keys of the review: moving between the entries, editing them, marking
+    /// them as done and adding the time spent on them.
+    fn handle_review_keys(
+        &mut self,
+        key_event: KeyEvent,
+        mut review: review::Review,
+    ) -> Result<()> {
+        if let Some(mut input) = review.duration.take() {
+            match key_event.code {
+                KeyCode::Esc => review.error = None,
+                KeyCode::Enter => match review::parse_duration(&input) {
+                    Some(duration) => {
+                        review.error = None;
+                        self.track(review.id(), duration);
+                        self.save()?;
+                    }
+                    None => {
+                        review.error = Some(i18n::fill("Not a duration: {}", &[&input]));
+                        review.duration = Some(input);
+                    }
+                },
+                KeyCode::Backspace => {
+                    editor::pop_grapheme(&mut input);
+                    review.error = None;
+                    review.duration = Some(input);
+                }
+                KeyCode::Char(c) => {
+                    input.push(c);
+                    review.error = None;
+                    review.duration = Some(input);
+                }
+                _ => review.duration = Some(input),
+            }
+            self.review = Some(review);
+            return Ok(());
+        }
+        match key_event.code {
+            KeyCode::Char('j') | KeyCode::Down | KeyCode::Enter => {
+                if review.index + 1 == review.ids.len() {
+                    return self.finish_review(&review);
+                }
+                review.index += 1;
+            }
+            KeyCode::Char('k') | KeyCode::Up => review.index = review.index.saturating_sub(1),
+            KeyCode::Char('e') => {
+                if let Some(item) = self.logs.iter().find(|i| i.id() == review.id()).cloned() {
+                    self.open_editor(item);
+                }
+            }
+            KeyCode::Char('x') => {
+                self.toggle_done(review.id());
+                self.save()?;
+            }
+            KeyCode::Char('t') => review.duration = Some(String::new()),
+            KeyCode::Char('q') | KeyCode::Esc => return self.finish_review(&review),
+            _ => {}
+        }
+        self.review = Some(review);
+        Ok(())
+    }
+
+    /// Ends the review with the summary of the day, copied now and printed on quitting.
+    fn finish_review(&mut self, review: &review::Review) -> Result<()> {
+        self.review = None;
+        let summary = review::summary(&self.logs, review.day);
+        clipboard::copy(&summary)?;
+        self.toasts.info(t("Copied the summary of the day"));
+        self.summary = Some(summary);
+        Ok(())
+    }
+
+    /// The summary of the last review, once.
+    pub fn take_summary(&mut self) -> Option<String> {
+        self.summary.take()
+    }
+
+    fn suspend(&mut self, terminal: &mut DefaultTerminal) -> Result<()> {
+        self.sync_draft()?;
+        execute!(std::io::stdout(), DisableBracketedPaste)?;
+        ratatui::restore();
+        signal::suspend()?;
+        enable_raw_mode()?;
+        execute!(
+            std::io::stdout(),
+            EnterAlternateScreen,
+            EnableBracketedPaste
+        )?;
+        terminal.clear()?;
+        Ok(())
+    }
+
+    pub fn run(&mut self, mut terminal: DefaultTerminal) -> Result<()> {
+        if self.first_run {
+            self.first_run = false;
+            self.add(Item::from(t(WELCOME).to_owned()));
+            self.save()?;
+        }
+        self.state.select_next();
+        if !self.read_only {
+            self.restore = draft::load();
+            let now = chrono::Local::now().naive_local();
+            if let Some(after) = self.settings.review_after
+                && review::due(after, now)
+            {
+                review::opened(now.date())?;
+                self.start_review(now.date());
+            }
+        }
+        let signals = signal::Signals::register()?;
+        // pasted text arrives as one event instead of keys that would run as commands
+        execute!(std::io::stdout(), EnableBracketedPaste)?;
+        let result = self.run_loop(&mut terminal, &signals);
+        execute!(std::io::stdout(), DisableBracketedPaste)?;
+        result
+    }
+
+    fn run_loop(
+        &mut self,
+        terminal: &mut DefaultTerminal,
+        signals: &signal::Signals,
+    ) -> Result<()> {
+        let mut last_draft = Instant::now();
+        // between events only the clock of the timer and the toasts move on, so idle ticks
+        // redraw when the second changes
+        let mut damaged = true;
+        let mut drawn = 0;
+        loop {
+            if signals.terminated() {
+                if self.dirty {
+                    self.save()?;
+                }
+                self.exit = true;
+            }
+            if self.exit {
+                self.sync_draft()?;
+                break Ok(());
+            }
+            let second = chrono::Local::now().timestamp();
+            if damaged || second != drawn {
+                terminal.draw(|frame| self.draw(frame))?;
+                damaged = false;
+                drawn = second;
+            }
+            if last_draft.elapsed() >= DRAFT_INTERVAL {
+                if let Err(e) = self.sync_draft() {
+                    self.toasts.error(e.to_string());
+                    damaged = true;
+                }
+                last_draft = Instant::now();
+            }
+            damaged |= self.toasts.expire();
+            if !event::poll(TICK_RATE)? {
+                continue;
+            }
+            let Ok(event) = event::read() else {
+                continue;
+            };
+            if let Event::Key(key_event) = event
+                && key_event.kind == event::KeyEventKind::Press
+                && key_event.code == KeyCode::Char('z')
+                && key_event.modifiers.contains(KeyModifiers::CONTROL)
+            {
+                self.suspend(terminal)?;
+                damaged = true;
+                continue;
+            }
+            self.handle_event(event);
+            damaged = true;
+        }
+    }
+
+    /// Reacts to a terminal event like the TUI does, e.g. to drive the app from tests. Errors
+    /// are shown in toasts, a failed save in its own dialog.
+    pub fn handle_event(&mut self, event: Event) {
+        if self.too_small {
+            // only quitting works, and the dialog asking about unsaved changes would not fit
+            // either, so they are saved like on a signal
+            if let Event::Key(key_event) = event
+                && key_event.kind == KeyEventKind::Press
+                && (key_event.code == KeyCode::Char('q')
+                    || key_event.code == KeyCode::Char('c')
+                        && key_event.modifiers.contains(KeyModifiers::CONTROL))
+            {
+                match if self.dirty { self.save() } else { Ok(()) } {
+                    Ok(()) => self.exit = true,
+                    Err(e) => self.toasts.error(e.to_string()),
+                }
+            }
+            return;
+        }
+        let key_event = match event {
+            Event::Key(key_event) => key_event,
+            Event::Paste(text) => return self.handle_paste(text),
+            _ => return,
+        };
+        match key_event.kind {
+            KeyEventKind::Press => {}
+            KeyEventKind::Repeat if self.repeats(key_event) => {}
+            _ => return,
+        }
+        if let Err(e) = self.handle_key(key_event)
+            && self.save_error.is_none()
+        {
+            self.toasts.error(e.to_string());
+        }
+    }
+
+    /// Whether holding down the key repeats it: typing and moving around do, commands like
+    /// `<d> <d>` do not.
+    fn repeats(&self, key_event: KeyEvent) -> bool {
+        let typing = self.edit.is_some()
+            || self.quick_add.is_some()
+            || self.filter_input.is_some()
+            || self.jump.is_some();
+        let ctrl = key_event.modifiers.contains(KeyModifiers::CONTROL);
+        match key_event.code {
+            KeyCode::Up
+            | KeyCode::Down
+            | KeyCode::Left
+            | KeyCode::Right
+            | KeyCode::PageUp
+            | KeyCode::PageDown
+            | KeyCode::Backspace
+            | KeyCode::Delete => true,
+            KeyCode::Char('j' | 'k') if !typing => true,
+            KeyCode::Char(_) | KeyCode::Enter | KeyCode::Tab => typing && !ctrl,
+            _ => false,
+        }
+    }
+
+    /// Inserts pasted text in one go: into the editor, the input being typed, or a new entry
+    /// when pasted into the table. Line breaks are only kept in the editor.
+    fn handle_paste(&mut self, text: String) {
+        let text = text.replace("\r\n", "\n").replace('\r', "\n");
+        let line = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        if self.save_error.is_some()
+            || self.confirm_quit
+            || self.duplicate.is_some()
+            || self.whats_new.is_some()
+            || self.restore.is_some()
+        {
+            return;
+        }
+        if let Some(ref mut input) = self.field_input {
+            input.push_str(&line);
+            return;
+        }
+        if let Some(item) = self.edit.clone() {
+            self.completion = None;
+            self.link_focus = None;
+            #[cfg(feature = "spellcheck")]
+            {
+                self.spelling.popup = None;
+            }
+            self.apply_edit(item, |s, c| editor::insert(s, c, &text));
+            return;
+        }
+        if let Some(ref mut palette) = self.palette {
+            palette.input.push_str(&line);
+            palette.selected = 0;
+            return;
+        }
+        if let Some(input) = self.review.as_mut().and_then(|r| r.duration.as_mut()) {
+            input.push_str(&line);
+            return;
+        }
+        if let Some(ref mut input) = self.jump
+            && self.view == View::Week
+        {
+            input.push_str(&line);
+            return;
+        }
+        if self.view != View::Log || self.read_only {
+            return;
+        }
+        if let Some(ref mut input) = self.quick_add {
+            input.push_str(&line);
+        } else if let Some(input) = self.filter_input.clone() {
+            self.update_filter(input + &line);
+        } else if !text.trim().is_empty() {
+            self.open_editor(Item::from(text.trim_end().to_owned()));
+            self.delete = None;
+        }
+    }
+
+    /// Hands `key_event` to the dialog, editor, prompt or view it is meant for.
+    fn handle_key(&mut self, key_event: KeyEvent) -> Result<()> {
+        if self.save_error.is_some() {
+            return self.handle_save_error_keys(key_event);
+        }
+        if self.confirm_quit {
+            return self.handle_confirm_quit_keys(key_event);
+        }
+        if self.duplicate.is_some() {
+            return self.handle_duplicate_keys(key_event);
+        }
+        if self.whats_new.is_some() {
+            return self.handle_whats_new_keys(key_event);
+        }
+        if self.restore.is_some() {
+            return self.handle_restore_keys(key_event);
+        }
+        if let Some(ref item) = self.edit {
+            let item = item.clone();
+            return self.handle_edit_keys(key_event, item);
+        }
+        if let Some(ref palette) = self.palette {
+            let palette = palette.clone();
+            return self.handle_palette_keys(key_event, palette);
+        }
+        if let Some(ref review) = self.review {
+            let review = review.clone();
+            return self.handle_review_keys(key_event, review);
+        }
+        if self.quick_add.is_none()
+            && self.filter_input.is_none()
+            && self.jump.is_none()
+            && is_alternate_key(key_event)
+        {
+            self.switch_view(self.previous_view);
+            return Ok(());
+        }
+        if self.view == View::Stats {
+            self.handle_stats_keys(key_event);
+            return Ok(());
+        }
+        if self.view == View::Week {
+            return self.handle_week_keys(key_event);
+        }
+        if let Some(ref input) = self.quick_add {
+            let input = input.clone();
+            return self.handle_quick_add_keys(key_event, input);
+        }
+        if let Some(ref input) = self.filter_input {
+            let input = input.clone();
+            self.handle_filter_keys(key_event, input);
+            return Ok(());
+        }
+        self.handle_main_keys(key_event)
+    }
+
+    /// Splits `content` into several entries if it exceeds the configured maximum length and the
+    /// configured policy asks for it.
+    pub fn split_content(&self, content: &str) -> Vec<String> {
+        match self.settings.max_length {
+            Some(max) if self.settings.on_exceed == LengthPolicy::Split => {
+                let chunks = editor::split_to_length(content, max);
+                if chunks.len() < 2 {
+                    return chunks;
+                }
+                chunks
+                    .into_iter()
+                    .map(|c| tag::inherit(content, &c))
+                    .collect()
+            }
+            _ => vec![content.to_owned()],
+        }
+    }
+
+    /// The shortest prefix of the id of `item` that no other loaded entry shares.
+    pub fn short_id(&self, item: &Item) -> String {
+        let mut id = item.uuid().simple().to_string();
+        id.truncate(self.index.abbrev());
+        id
+    }
+
+    /// Condenses `item` into the text shown in its table row.
+    pub fn preview(&self, item: &Item) -> String {
+        let content = item.content();
+        let preview = match self.settings.preview_mode {
+            PreviewMode::FirstLine => match editor::first_line(&content) {
+                (line, true) => format!("{line} …"),
+                (line, false) => line.to_owned(),
+            },
+            PreviewMode::Flatten => content.replace("\n", " "),
+        };
+        match self.settings.preview_length {
+            Some(max) => editor::summarize(&preview, max),
+            None => preview,
+        }
+    }
+
+    /// Renders the projects, tags and people of an entry as colored chips.
+    fn chips(&self, markers: &[String]) -> Line<'static> {
+        let mut spans = Vec::new();
+        for marker in markers {
+            let color = if self.edit.is_none() {
+                tag::color(marker, &self.settings.colors)
+            } else {
+                COLOR_TERTIARY_DARK
+            };
+            spans.push(Span::styled(
+                format!(" {marker} "),
+                Style::new()
+                    .fg(color)
+                    .add_modifier(Modifier::REVERSED | Modifier::BOLD),
+            ));
+            spans.push(Span::raw(" "));
+        }
+        Line::from(spans)
+    }
+
+    pub fn exceeds_max_length(&self, content: &str) -> bool {
+        self.settings
+            .max_length
+            .is_some_and(|max| editor::len(content) > max)
+    }
+
+    /// Adds `item` or replaces the stored entry with the same id.
+    fn store(&mut self, item: Item) {
+        let Some(stored) = self.logs.iter_mut().find(|l| l.id() == item.id()) else {
+            self.add(item);
+            return;
+        };
+        if stored.fields() == item.fields() {
+            self.update(item.id(), item.content());
+            return;
+        }
+        self.audit
+            .touch(audit::Kind::Update, stored.uuid(), Some(stored));
+        self.journal.record(vec![Change::Updated(stored.clone())]);
+        stored.update(item.content());
+        stored.set_fields(item.fields());
+        self.index.insert(stored);
+        self.dirty = true;
+        self.hook_id(hook::Event::Update, &item.id());
+        self.sort();
+    }
+
+    /// Custom fields of `item`: the ones declared in the settings, then any others it has a
+    /// value for, e.g. after a field was removed from the settings.
+    fn fields(&self, item: &Item) -> Vec<(String, FieldType)> {
+        let mut fields: Vec<(String, FieldType)> = self
+            .settings
+            .fields
+            .iter()
+            .map(|(name, kind)| (name.clone(), *kind))
+            .collect();
+        for name in item.fields().keys() {
+            if !self.settings.fields.contains_key(name) {
+                fields.push((name.clone(), FieldType::String));
+            }
+        }
+        fields
+    }
+
+    /// Sets the custom field `name` of an entry, checking `value` against its declared type.
+    pub fn set_field<T: AsRef<str>>(&mut self, id: T, name: &str, value: &str) -> Result<()> {
+        let value = match (value.trim(), self.settings.fields.get(name)) {
+            ("", _) => String::new(),
+            (value, Some(kind)) => kind
+                .parse(value)
+                .map_err(|e| eyre!("invalid value for {name}: {e}"))?,
+            (value, None) => value.to_owned(),
+        };
+        if let Some(item) = self.logs.iter_mut().find(|i| i.id() == id.as_ref())
+            && item.field(name).unwrap_or_default() != value
+        {
+            self.audit
+                .touch(audit::Kind::Update, item.uuid(), Some(item));
+            self.journal.record(vec![Change::Updated(item.clone())]);
+            item.set_field(name, value);
+            self.dirty = true;
+            self.hook_id(hook::Event::Update, id.as_ref());
+        }
+        Ok(())
+    }
+
+    /// Orders pinned entries first, then everything by the current sort order.
+    fn sort(&mut self) {
+        match self.sort_order {
+            SortOrder::Created => self.logs.sort_by_key(|l| {
+                (
+                    std::cmp::Reverse(l.pinned()),
+                    std::cmp::Reverse(l.created()),
+                )
+            }),
+            SortOrder::Alphabetical => self.logs.sort_by(|a, b| {
+                b.pinned()
+                    .cmp(&a.pinned())
+                    .then_with(|| collate::compare(&a.content(), &b.content()))
+            }),
+        }
+    }
+
+    pub fn toggle_sort(&mut self) {
+        let selected = self.selected().map(Item::id);
+        self.sort_order = self.sort_order.toggle();
+        self.sort();
+        if let Some(id) = selected {
+            self.select(&id);
+        }
+    }
+
+    /// Indices into `logs` of the entries shown in the table.
+    fn visible(&self) -> Vec<usize> {
+        let visible = match &self.filter {
+            Some(query) => query.filter(&self.logs, &self.index),
+            None => (0..self.logs.len()).collect(),
+        };
+        match self.period {
+            Some(period) => visible
+                .into_iter()
+                .filter(|&i| period.contains(self.logs[i].created().date_naive()))
+                .collect(),
+            None => visible,
+        }
+    }
+
+    /// The day of the selected entry, or today.
+    fn selected_day(&self) -> chrono::NaiveDate {
+        self.selected()
+            .map(|item| item.created().date_naive())
+            .unwrap_or_else(|| chrono::Local::now().date_naive())
+    }
+
+    /// Limits the table to `period`, loading the months it covers first.
+    fn show_period(&mut self, period: Option<browse::Period>) -> Result<()> {
+        if let Some(period) = period {
+            for month in period.months() {
+                let items = self.shards.load(&month)?;
+                self.extend(items);
+            }
+        }
+        self.period = period;
+        self.state.select_first();
+        Ok(())
+    }
+
+    fn selected(&self) -> Option<&Item> {
+        let index = *self.visible().get(self.state.selected()?)?;
+        self.logs.get(index)
+    }
+
+    fn select(&mut self, id: &str) {
+        let position = self
+            .visible()
+            .into_iter()
+            .position(|i| self.logs[i].id() == id);
+        self.state.select(position);
+    }
+
+    /// Shows only entries matching `query`, or all entries for `None`.
+    fn set_filter(&mut self, query: Option<Query>) {
+        let selected = self.selected().map(Item::id);
+        self.filter = query;
+        self.state.select_first();
+        if let Some(id) = selected
+            && self.visible().iter().any(|&i| self.logs[i].id() == id)
+        {
+            self.select(&id);
         }
     }
 
-    pub fn handle_edit_keys(&mut self, key_event: KeyEvent, item: Item) -> Result<()> {
-        match key_event.code {
-            KeyCode::Backspace => {
-                if let Some(item) = handle_backspace(item.clone(), key_event) {
-                    self.edit = Some(item);
-                }
-            }
-            KeyCode::Esc => {
-                self.edit = None;
-            }
-            KeyCode::Char('o') | KeyCode::Enter
-                if key_event.modifiers.contains(KeyModifiers::CONTROL) =>
-            {
-                if !item
-                    .content()
-                    .replace("\n", "")
-                    .replace("\t", "")
-                    .is_empty()
-                {
-                    if self.logs.iter().any(|l| l.id() == item.id()) {
-                        self.update(item.id(), item.content());
-                    } else {
-                        self.logs.push(item.clone());
-                        self.logs.sort_by_key(|l| std::cmp::Reverse(l.created()));
-                    }
-                    self.edit = None;
-                    self.save()?;
-                }
+    /// Marks the entry as the one being worked on, or stops it if it already is. Stopping an
+    /// entry, also by marking another one, adds the time since it was marked to it.
+    pub fn toggle_current<T: AsRef<str>>(&mut self, id: T) {
+        if let Some(current) = self.current.take() {
+            if let Some(item) = self.logs.iter_mut().find(|i| i.id() == current.id) {
+                self.audit
+                    .touch(audit::Kind::Update, item.uuid(), Some(item));
+                item.track(current.elapsed());
             }
-            KeyCode::Enter => {
-                let mut tmp = item.clone();
-                let mut s = tmp.content();
-                s.push('\n');
-                tmp.update(s);
-                self.edit = Some(tmp);
+            if current.id == id.as_ref() {
+                self.dirty = true;
+                return;
             }
-            KeyCode::Char(key) => {
-                if key == 'h' && key_event.modifiers.contains(KeyModifiers::CONTROL) {
-                    if let Some(item) = handle_backspace(item.clone(), key_event) {
-                        self.edit = Some(item);
-                    }
-                } else if key == 'c' && key_event.modifiers.contains(KeyModifiers::CONTROL) {
-                    self.edit = None;
-                } else {
-                    let mut tmp = item.clone();
-                    let mut s = tmp.content();
-                    s.push(key);
-                    tmp.update(s);
-                    self.edit = Some(tmp);
-                }
+        }
+        self.current = Some(focus::Current::new(id.as_ref().to_owned()));
+        self.dirty = true;
+    }
+
+    pub fn toggle_pin<T: AsRef<str>>(&mut self, id: T) {
+        if let Some(item) = self.logs.iter_mut().find(|i| i.id() == id.as_ref()) {
+            self.audit
+                .touch(audit::Kind::Update, item.uuid(), Some(item));
+            item.toggle_pin();
+            self.dirty = true;
+        }
+        self.hook_id(hook::Event::Update, id.as_ref());
+        self.sort();
+        self.select(id.as_ref());
+    }
+
+    pub fn toggle_done<T: AsRef<str>>(&mut self, id: T) {
+        if let Some(item) = self.logs.iter_mut().find(|i| i.id() == id.as_ref()) {
+            self.audit
+                .touch(audit::Kind::Update, item.uuid(), Some(item));
+            item.toggle_done();
+            self.dirty = true;
+        }
+        self.hook_id(hook::Event::Update, id.as_ref());
+    }
+
+    /// Adds `duration` to the time tracked on the entry.
+    pub fn track<T: AsRef<str>>(&mut self, id: T, duration: chrono::TimeDelta) {
+        if let Some(item) = self.logs.iter_mut().find(|i| i.id() == id.as_ref()) {
+            self.audit
+                .touch(audit::Kind::Update, item.uuid(), Some(item));
+            item.track(duration);
+            self.dirty = true;
+        }
+        self.hook_id(hook::Event::Update, id.as_ref());
+    }
+
+    pub fn toggle_billable<T: AsRef<str>>(&mut self, id: T) {
+        if let Some(item) = self.logs.iter_mut().find(|i| i.id() == id.as_ref()) {
+            self.audit
+                .touch(audit::Kind::Update, item.uuid(), Some(item));
+            item.toggle_billable();
+            self.dirty = true;
+        }
+        self.hook_id(hook::Event::Update, id.as_ref());
+    }
+
+    pub fn add(&mut self, item: Item) {
+        self.audit.touch(audit::Kind::Add, item.uuid(), None);
+        self.hook(hook::Event::Add, &item);
+        self.index.insert(&item);
+        self.logs.push(item);
+        self.dirty = true;
+        self.sort();
+    }
+
+    /// The daily note of today, if it was started.
+    pub fn today(&self) -> Option<&Item> {
+        let today = chrono::Local::now().date_naive();
+        self.logs
+            .iter()
+            .find(|i| i.daily() && i.created().date_naive() == today)
+    }
+
+    /// Opens the daily note of today in the editor, a new one is added once it is saved.
+    pub fn open_today(&mut self) {
+        let item = self.today().cloned().unwrap_or_else(Item::daily_note);
+        self.open_editor(item);
+    }
+
+    /// Adds `text` as a timestamped line to the daily note of today, starting it if needed.
+    pub fn append_today(&mut self, text: &str) {
+        match self.today().map(Item::id) {
+            Some(id) => self.append(&id, text),
+            None => {
+                let mut item = Item::daily_note();
+                item.update(format!(
+                    "{}{}{text}",
+                    item.content().trim_end(),
+                    note_prefix()
+                ));
+                self.add(item);
             }
-            _ => {}
         }
-        Ok(())
     }
 
-    pub fn handle_main_keys(&mut self, key_event: KeyEvent) -> Result<()> {
-        match key_event.code {
-            KeyCode::Char('q') | KeyCode::Esc => self.exit = true,
-            KeyCode::Char('e') | KeyCode::Char(' ') | KeyCode::Enter => match self.state.selected()
-            {
-                Some(selected) => {
-                    if let Some(v) = self.logs.get(selected) {
-                        self.edit = Some(v.clone());
-                    } else {
-                        self.edit = Some(Item::new());
-                    }
-                }
-                None => self.edit = Some(Item::new()),
-            },
-            KeyCode::Char('j') | KeyCode::Down => {
-                self.state.select_next();
-                self.delete = None;
+    /// A recent entry with the same content as `content`, ignoring case, accents and
+    /// whitespace.
+    pub fn duplicate_of(&self, content: &str) -> Option<&Item> {
+        let window = chrono::Duration::minutes(self.settings.duplicates.within_minutes.into());
+        let since = chrono::Local::now() - window;
+        let key = collate::key(content);
+        self.logs.iter().filter(|i| i.created() >= since).find(|i| {
+            collate::key(&i.content())
+                .split_whitespace()
+                .eq(key.split_whitespace())
+        })
+    }
+
+    /// Adds `item` unless it repeats a recent entry, in which case the configured
+    /// [`DuplicateAction`] applies.
+    pub fn add_unique(&mut self, item: Item) -> Added {
+        let Some(duplicate) = self.duplicate_of(&item.content()).cloned() else {
+            self.add(item);
+            return Added::New;
+        };
+        match self.settings.duplicates.action {
+            DuplicateAction::Allow => {
+                self.add(item);
+                Added::New
             }
-            KeyCode::Char('k') | KeyCode::Up => {
-                self.state.select_previous();
-                self.delete = None;
+            DuplicateAction::Skip => Added::Skipped(duplicate),
+            DuplicateAction::Merge => {
+                self.update(duplicate.id(), item.content());
+                Added::Merged(duplicate)
             }
-            KeyCode::Char('g') | KeyCode::Home => {
-                self.state.select_first();
-                self.delete = None;
+            DuplicateAction::Prompt => Added::Confirm { duplicate, item },
+        }
+    }
+
+    /// Resolves `selector` to an entry, loading older months until it is found. A position
+    /// long enough to be an abbreviated id refers to the entry whose id it starts, if any.
+    pub fn find(&mut self, selector: &Selector) -> Result<Item> {
+        if let Selector::Id(prefix) = selector
+            && uuid::Uuid::try_parse(prefix).is_err()
+        {
+            return self.resolve(prefix);
+        }
+        if let Selector::Nth(n) = selector
+            && n.to_string().len() >= 4
+        {
+            self.load_all()?;
+            if !self.index.resolve(&n.to_string()).is_empty() {
+                return self.resolve(&n.to_string());
             }
-            KeyCode::Char('G') | KeyCode::End => {
-                self.state.select_last();
-                self.delete = None;
+        }
+        let lookup = |logs: &[Item]| match selector {
+            Selector::Id(id) => logs.iter().find(|i| i.id() == *id).cloned(),
+            Selector::Last | Selector::Nth(_) => {
+                let n = match selector {
+                    Selector::Nth(n) => *n,
+                    _ => 1,
+                };
+                let mut recent: Vec<&Item> = logs.iter().collect();
+                recent.sort_by_key(|i| std::cmp::Reverse(i.created()));
+                recent.get(n - 1).map(|i| (*i).clone())
             }
-            KeyCode::Char('o') => {
-                self.edit = Some(Item::new());
-                self.delete = None;
+        };
+        loop {
+            if let Some(item) = lookup(&self.logs) {
+                return Ok(item);
             }
-            KeyCode::Char('d') => {
-                let curr = self.state.selected();
-                match curr {
-                    None => {
-                        self.delete = None;
-                    }
-                    Some(c) => {
-                        if self.delete == curr {
-                            let id = self.logs[c].id();
-                            self.delete = None;
-                            self.remove(id);
-                            self.save()?;
-                        } else {
-                            self.delete = curr;
-                        }
-                    }
-                }
+            if !self.load_older()? {
+                return Err(eyre!("no entry {selector}"));
             }
-            _ => {}
         }
-        Ok(())
     }
 
-    pub fn run(&mut self, mut terminal: DefaultTerminal) -> Result<()> {
-        self.state.select_next();
-        loop {
-            if self.exit {
-                break Ok(());
-            }
-            terminal.draw(|frame| self.draw(frame))?;
-            if let Ok(event) = event::read()
-                && let Event::Key(key_event) = event
-                && key_event.kind == event::KeyEventKind::Press
-            {
-                if let Some(ref item) = self.edit {
-                    let item = item.clone();
-                    self.handle_edit_keys(key_event, item)?;
-                    continue;
-                }
-                self.handle_main_keys(key_event)?;
-            }
+    /// Looks an entry up by an abbreviated id. Every month is loaded, so a prefix is only
+    /// accepted when no entry at all shares it.
+    fn resolve(&mut self, prefix: &str) -> Result<Item> {
+        if prefix.len() < 4 {
+            return Err(eyre!(
+                "id {prefix} is too short, give at least 4 characters"
+            ));
+        }
+        self.load_all()?;
+        match self.index.resolve(prefix).as_slice() {
+            [] => Err(eyre!("no entry {prefix}")),
+            [id] => self
+                .logs
+                .iter()
+                .find(|i| i.uuid() == *id)
+                .cloned()
+                .ok_or_else(|| eyre!("no entry {prefix}")),
+            ids => Err(eyre!(
+                "id {prefix} is ambiguous, it matches {}",
+                ids.iter()
+                    .map(|id| id.simple().to_string()[..self.index.abbrev()].to_owned())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )),
         }
     }
 
-    pub fn add(&mut self, item: Item) {
-        self.logs.push(item);
-        self.logs.sort_by_key(|l| std::cmp::Reverse(l.created()));
+    pub fn attach<T: AsRef<str>>(&mut self, id: T, attachment: Attachment) {
+        if let Some(item) = self.logs.iter_mut().find(|i| i.id() == id.as_ref()) {
+            self.audit
+                .touch(audit::Kind::Update, item.uuid(), Some(item));
+            self.journal.record(vec![Change::Updated(item.clone())]);
+            item.attach(attachment);
+            self.dirty = true;
+        }
+        self.hook_id(hook::Event::Update, id.as_ref());
+    }
+
+    /// Adds `text` as a timestamped line to the end of an entry.
+    pub fn append<T: AsRef<str>>(&mut self, id: T, text: &str) {
+        if let Some(item) = self.logs.iter().find(|i| i.id() == id.as_ref()) {
+            let content = format!("{}{}{text}", item.content().trim_end(), note_prefix());
+            self.update(id.as_ref(), &content);
+        }
     }
 
     pub fn update<T: AsRef<str>>(&mut self, id: T, content: T) {
         if let Some(item) = self.logs.iter_mut().find(|i| i.id() == id.as_ref()) {
+            let changed = item.content() != content.as_ref();
+            if changed {
+                self.journal.record(vec![Change::Updated(item.clone())]);
+            }
+            self.audit
+                .touch(audit::Kind::Update, item.uuid(), Some(item));
             item.update(content.as_ref().to_owned());
+            self.index.insert(item);
+            self.dirty = true;
+            if changed {
+                self.hook_id(hook::Event::Update, id.as_ref());
+            }
         }
-        self.logs.sort_by_key(|l| std::cmp::Reverse(l.created()));
+        self.sort();
     }
 
     pub fn remove<T: AsRef<str>>(&mut self, id: T) {
+        for item in self.logs.clone().iter().filter(|i| i.id() == id.as_ref()) {
+            self.hook(hook::Event::Delete, item);
+        }
+        let removed = self
+            .logs
+            .iter()
+            .filter(|i| i.id() == id.as_ref())
+            .cloned()
+            .map(Change::Removed)
+            .collect();
+        self.journal.record(removed);
+        if self.current.as_ref().is_some_and(|c| c.id == id.as_ref()) {
+            self.current = None;
+        }
+        if self.logs.iter().any(|i| i.id() == id.as_ref()) {
+            self.dirty = true;
+            self.tombstones.push(merge::Tombstone {
+                id: id.as_ref().to_owned(),
+                deleted: chrono::Local::now(),
+            });
+        }
+        for item in self.logs.iter().filter(|i| i.id() == id.as_ref()) {
+            self.audit
+                .touch(audit::Kind::Delete, item.uuid(), Some(item));
+            self.index.remove(item.uuid());
+        }
         self.logs.retain(|i| i.id() != id.as_ref());
     }
 
-    pub fn save(&self) -> Result<()> {
-        let output = serde_json::to_string_pretty(&self)?;
-        fs::write(Self::config_path(), output)?;
+    /// Merges another copy of the log, e.g. from a different machine, into this one.
+    pub fn merge(&mut self, other: App) -> Result<merge::MergeReport> {
+        self.load_all()?;
+        let known: HashSet<uuid::Uuid> = self.logs.iter().map(Item::uuid).collect();
+        let (report, changes) = merge::merge(
+            &mut self.logs,
+            &mut self.tombstones,
+            other.logs,
+            other.tombstones,
+        );
+        for change in &changes {
+            let (Change::Removed(item) | Change::Updated(item)) = change;
+            self.audit
+                .touch(audit::Kind::Merge, item.uuid(), Some(item));
+        }
+        for item in self.logs.iter().filter(|i| !known.contains(&i.uuid())) {
+            self.audit.touch(audit::Kind::Merge, item.uuid(), None);
+        }
+        self.journal.record(changes);
+        self.index = Index::new(&self.logs);
+        self.dirty |= report.added + report.updated + report.removed > 0;
+        self.sort();
+        Ok(report)
+    }
+
+    /// Reverts the most recent destructive operation, returning the restored entries.
+    pub fn undo(&mut self) -> Result<Option<Vec<Item>>> {
+        let Some(entry) = self.journal.pop() else {
+            return Ok(None);
+        };
+        // the entries may belong to months that were not loaded yet
+        for change in &entry.changes {
+            let (Change::Removed(item) | Change::Updated(item)) = change;
+            self.load_month(item)?;
+        }
+        let restored = entry
+            .changes
+            .into_iter()
+            .rev()
+            .map(|change| {
+                let (event, item) = match change {
+                    Change::Removed(item) => (hook::Event::Add, item),
+                    Change::Updated(item) => (hook::Event::Update, item),
+                };
+                self.hook(event, &item);
+                let before = self.logs.iter().find(|i| i.id() == item.id());
+                self.audit.touch(audit::Kind::Undo, item.uuid(), before);
+                self.tombstones.retain(|t| t.id != item.id());
+                self.logs.retain(|i| i.id() != item.id());
+                self.index.insert(&item);
+                self.logs.push(item.clone());
+                item
+            })
+            .collect();
+        self.dirty = true;
+        self.sort();
+        Ok(Some(restored))
+    }
+
+    /// Remembers the change for the hooks run after the next save.
+    fn hook(&mut self, event: hook::Event, item: &Item) {
+        if self.settings.hooks.wants(event) {
+            self.hook_changes.push(hook::Change {
+                event,
+                item: item.clone(),
+            });
+        }
+    }
+
+    fn hook_id(&mut self, event: hook::Event, id: &str) {
+        if let Some(item) = self.logs.iter().find(|i| i.id() == id).cloned() {
+            self.hook(event, &item);
+        }
+    }
+
+    /// Whether the TUI quits after the current event.
+    pub fn exiting(&self) -> bool {
+        self.exit
+    }
+
+    /// Problems that were not shown yet, e.g. failed hooks.
+    pub fn take_errors(&mut self) -> Vec<String> {
+        self.toasts.take_errors()
+    }
+
+    pub fn save(&mut self) -> Result<()> {
+        if self.read_only {
+            return Err(eyre!("lw is running in safe mode, changes cannot be saved"));
+        }
+        let saving = signal::Saving::start();
+        let result = self.write();
+        drop(saving);
+        self.save_error = result.as_ref().err().map(ToString::to_string);
+        result
+    }
+
+    fn write(&mut self) -> Result<()> {
+        if self.in_memory {
+            self.audit.take(&self.logs);
+            self.dirty = false;
+            return Ok(());
+        }
+        let compression = self.settings.compression;
+        self.shards.save(&self.logs, compression)?;
+        let header = Header {
+            version: &self.version,
+            logs: &[],
+            tombstones: &self.tombstones,
+            current: &self.current,
+        };
+        storage::write(Self::config_path(), &header, compression)?;
+        self.journal.save()?;
+        // taken only now, so a failed save keeps them for its retry
+        let records = self.audit.take(&self.logs);
+        if self.settings.audit.unwrap_or(true)
+            && let Err(e) = audit::append(&records)
+        {
+            self.audit.keep(records);
+            return Err(e);
+        }
+        self.dirty = false;
+        let changes = std::mem::take(&mut self.hook_changes);
+        for failure in self.settings.hooks.run(&changes) {
+            self.toasts.error(failure);
+        }
         Ok(())
     }
 }
@@ -346,79 +3090,106 @@ impl Widget for &mut App {
             COLOR_TERTIARY_DARK
         };
 
-        let title = Line::from(Span::styled(
-            " Log Your Work ",
+        let mut title = Line::from(Span::styled(
+            format!(" {} ", t("Log Your Work")),
             Style::default().fg(primary_color).bold(),
         ));
+        if let Some(ref goal) = self.settings.goal {
+            let today = chrono::Local::now().date_naive();
+            if goal.applies(today) {
+                let mut progress = goal::Progress::on(&self.logs, today);
+                // the entry being worked on counts towards the goal while its timer runs
+                if let Some(ref current) = self.current
+                    && self
+                        .logs
+                        .iter()
+                        .any(|i| i.id() == current.id && i.created().date_naive() == today)
+                {
+                    progress.tracked += current.elapsed();
+                }
+                let (mark, color) = if goal.met(&progress) {
+                    ("✓ ", COLOR_PRIMARY)
+                } else {
+                    ("", COLOR_SECONDARY)
+                };
+                title.push_span(Span::styled(
+                    format!("[{mark}{} {}] ", t("today"), goal.describe(&progress)),
+                    Style::default().fg(color).bold(),
+                ));
+            }
+        }
+        if let Some(period) = self.period {
+            title.push_span(Span::styled(
+                format!("[{}] ", period.title()),
+                Style::default().fg(COLOR_SECONDARY).bold(),
+            ));
+        }
+        if let Some(ref query) = self.filter {
+            title.push_span(Span::styled(
+                format!("[{query}] "),
+                Style::default().fg(COLOR_SECONDARY).bold(),
+            ));
+        }
+        if self.dirty {
+            title.push_span(Span::styled(
+                format!("[{}] ", t("unsaved")),
+                Style::default().fg(COLOR_SECONDARY).bold(),
+            ));
+        }
+        if self.read_only {
+            title.push_span(Span::styled(
+                format!("[{}] ", t("safe mode: read-only")),
+                Style::default().fg(Color::Red).bold(),
+            ));
+        }
 
-        let instructions = Line::from(vec![
-            Span::raw(" New "),
-            Span::styled(
-                "<o>",
-                Style::default()
-                    .fg(primary_color)
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::raw(" Select "),
-            Span::styled(
-                "<e> | <Enter> | <Space>",
-                Style::default()
-                    .fg(primary_color)
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::raw(" Down "),
-            Span::styled(
-                "<j>",
-                Style::default()
-                    .fg(primary_color)
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::raw(" Up "),
-            Span::styled(
-                "<k>",
-                Style::default()
-                    .fg(primary_color)
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::raw(" Bottom "),
-            Span::styled(
-                "<G>",
-                Style::default()
-                    .fg(primary_color)
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::raw(" Top "),
-            Span::styled(
-                "<g>",
-                Style::default()
-                    .fg(primary_color)
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::raw(" Quit "),
-            Span::styled(
-                "<q> | <ESC>",
-                Style::default()
-                    .fg(primary_color)
-                    .add_modifier(Modifier::BOLD),
-            ),
+        let quit = if self.settings.quit_on_esc.unwrap_or(true) {
+            "<q> | <ESC>"
+        } else {
+            "<q>"
+        };
+        let mut hints = if self.read_only {
+            vec![]
+        } else {
+            vec![
+                ("New", "<o>"),
+                ("Quick Add", "<i>"),
+                ("Pin", "<p>"),
+                ("Undo", "<u>"),
+            ]
+        };
+        hints.push(("Stats", "<s>"));
+        if !self.read_only {
+            hints.push(("Select", "<e> | <Enter> | <Space>"));
+        }
+        hints.extend([
+            ("Down", "<j>"),
+            ("Up", "<k>"),
+            ("Bottom", "<G>"),
+            ("Top", "<g>"),
+            ("Quit", quit),
         ]);
 
         let block = Block::bordered()
             .title(title.centered())
-            .title_bottom(instructions.centered())
             .title_style(Color::White)
             .border_set(border::THICK)
             .border_style(Color::White);
 
-        let header = [
-            "Log", // "Modified",
-            "Created",
-        ]
-        .into_iter()
-        .map(Cell::from)
-        .collect::<Row>()
-        .style(Style::default().fg(teritary_color).bold())
-        .height(1);
+        let spacious = self.density == Density::Spacious;
+
+        let mut header = vec!["", t("Log")];
+        if spacious {
+            header.push("Id");
+            header.push(t("Modified"));
+        }
+        header.push(t("Created"));
+        let header = header
+            .into_iter()
+            .map(Cell::from)
+            .collect::<Row>()
+            .style(Style::default().fg(teritary_color).bold())
+            .height(1);
 
         let mut highlight_style = Style::new().fg(primary_color).bold();
 
@@ -428,95 +3199,348 @@ impl Widget for &mut App {
             Color::White
         };
 
-        let items: Vec<Row> = if self.logs.is_empty() {
-            vec![Row::new(vec!["Nothing here yet"])]
+        let visible: Vec<&Item> = self.visible().into_iter().map(|i| &self.logs[i]).collect();
+        if self.delete.is_some_and(|index| index < visible.len()) {
+            highlight_style = Style::new().fg(Color::LightRed).bold();
+        }
+        // rows cannot be smaller than a line, so at most this many are in view wherever the
+        // table scrolls to on its way to the selection, which is past the end after `<G>`
+        let last = visible.len().saturating_sub(1);
+        let selected = self.state.selected().unwrap_or(0).min(last);
+        let offset = self.state.offset().min(last);
+        let window = offset.min(selected).saturating_sub(area.height.into())
+            ..offset.max(selected) + usize::from(area.height);
+
+        // wide enough for the most badges of an entry in view
+        let badges_width = visible
+            .iter()
+            .skip(window.start)
+            .take(window.len())
+            .map(|item| item.badges().width() as u16)
+            .fold(BADGES_WIDTH, u16::max);
+        let timestamp_columns = if spacious { 2 } else { 1 };
+        let id_width = self.index.abbrev() as u16;
+        // borders, highlight symbol, column spacing, badges, the id and the timestamp columns
+        let content_width = usize::from(area.width.saturating_sub(
+            2 + 2
+                + 1
+                + badges_width
+                + if spacious { id_width + 1 } else { 0 }
+                + timestamp_columns * (TIMESTAMP_WIDTH + 1),
+        ));
+        let mut rows = std::mem::take(&mut self.rows);
+        rows.prepare(
+            (self.settings.preview_mode, self.settings.preview_length),
+            self.logs.len(),
+        );
+        let items: Vec<Row> = if visible.is_empty() {
+            vec![Row::new(vec![
+                Cell::from(""),
+                Cell::from(match self.filter {
+                    Some(ref query) => i18n::fill("No entries match {}", &[&query.to_string()]),
+                    None => t("Nothing here yet").to_owned(),
+                }),
+            ])]
         } else {
-            self.logs
+            visible
                 .iter()
                 .enumerate()
                 .map(|(i, item)| {
-                    [
-                        item.content().replace("\n", " "),
-                        // item.modified().format("%Y-%m-%d %H:%M:%S").to_string(),
-                        item.created().format("%Y-%m-%d %H:%M:%S").to_string(),
-                    ]
-                    .into_iter()
-                    .map(|c| {
-                        Cell::from(Text::from(c).style({
-                            let s = Style::new();
-
-                            if let Some(index) = self.delete
-                                && i == index
-                            {
-                                highlight_style = Style::new().fg(Color::LightRed).bold();
-                                s.fg(Color::LightRed).bold()
-                            } else {
-                                s
-                            }
+                    // separates the pinned section from the chronological list
+                    let margin = if item.pinned() && visible.get(i + 1).is_some_and(|n| !n.pinned())
+                    {
+                        1
+                    } else {
+                        0
+                    };
+                    // rows out of view only take up their space
+                    if !window.contains(&i) {
+                        return Row::default()
+                            .height(self.density.height())
+                            .bottom_margin(margin);
+                    }
+                    let style = if self.delete == Some(i) {
+                        Style::new().fg(Color::LightRed).bold()
+                    } else {
+                        Style::new()
+                    };
+
+                    let cached = rows.get(item, |item| self.preview(item));
+                    let preview = match self.rules.icon(item) {
+                        Some(icon) => format!("{icon} {}", cached.preview),
+                        None => cached.preview.clone(),
+                    };
+                    let style = match self.rules.color(item) {
+                        Some(color) if self.edit.is_none() && style == Style::new() => {
+                            style.fg(color)
+                        }
+                        _ => style,
+                    };
+                    let mut content =
+                        Text::from(editor::truncate_to_width(&preview, content_width)).style(style);
+                    let chips = self.chips(&cached.markers);
+                    if self.density != Density::Compact && !chips.spans.is_empty() {
+                        content.push_line(chips);
+                    }
+
+                    let mut cells = vec![
+                        Cell::from(Text::from(item.badges()).style(style)),
+                        Cell::from(content),
+                    ];
+                    if spacious {
+                        cells.push(Cell::from(
+                            Text::from(self.short_id(item)).style(style.fg(COLOR_TERTIARY_DARK)),
+                        ));
+                        cells.push(Cell::from(Text::from(cached.modified.clone()).style(style)));
+                    }
+                    cells.push(Cell::from(Text::from(cached.created.clone()).style(style)));
+
+                    Row::new(cells)
+                        .style(Style::new().fg(if item.pinned() && self.edit.is_none() {
+                            COLOR_SECONDARY
+                        } else {
+                            row_text_color
                         }))
-                    })
-                    .collect::<Row>()
-                    .style(Style::new().fg(row_text_color))
-                    .height(2)
+                        .height(self.density.height())
+                        .bottom_margin(margin)
                 })
                 .collect()
         };
+        self.rows = rows;
+
+        let mut widths = vec![Constraint::Length(badges_width), Constraint::Fill(1)];
+        if spacious {
+            widths.push(Constraint::Length(id_width));
+        }
+        widths.extend(vec![
+            Constraint::Length(TIMESTAMP_WIDTH);
+            timestamp_columns.into()
+        ]);
+
+        let total = visible.len();
+        // older months that were not read yet are not counted
+        let position = self.state.selected().filter(|_| total > 0).map(|selected| {
+            let more = if self.shards.is_complete() { "" } else { "+" };
+            format!(
+                " {} ",
+                i18n::fill(
+                    "item {}/{}",
+                    &[
+                        &(selected.min(total - 1) + 1).to_string(),
+                        &format!("{total}{more}")
+                    ]
+                )
+            )
+        });
+        // the centered hints stay clear of the position on the right, and of as much on the
+        // left, between the corners of the border
+        let reserved = 2 * position
+            .as_ref()
+            .map_or(0, |position| position.width() as u16);
+        let hints = fit_hints(&hints, area.width.saturating_sub(2 + reserved));
+        let block = block.title_bottom(key_hints(&hints, primary_color).centered());
+        let block = match position {
+            Some(position) => block.title_bottom(
+                Line::from(Span::styled(position, Style::default().fg(teritary_color)))
+                    .right_aligned(),
+            ),
+            None => block,
+        };
 
-        let table = Table::new(items, [Constraint::Min(200), Constraint::Min(20)])
+        let table = Table::new(items, widths)
             .block(block)
             .header(header)
             .highlight_symbol("> ")
             .row_highlight_style(highlight_style)
             .highlight_spacing(HighlightSpacing::Always);
 
-        StatefulWidget::render(table, area, buf, &mut self.state);
-    }
-}
+        let current = self
+            .current
+            .as_ref()
+            .and_then(|c| Some((c, self.logs.iter().find(|i| i.id() == c.id)?)));
+        let area = if let Some((current, item)) = current {
+            let [banner_area, table_area] =
+                Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(area);
+            let elapsed = focus::format(current.elapsed());
+            Paragraph::new(Line::from(vec![
+                Span::styled(
+                    format!(" ● {elapsed} "),
+                    Style::default().fg(Color::Black).bg(COLOR_PRIMARY).bold(),
+                ),
+                Span::styled(
+                    format!(" {} ", t("Working on")),
+                    Style::default().fg(COLOR_SECONDARY).bold(),
+                ),
+                Span::raw(editor::truncate_to_width(
+                    &self.preview(item),
+                    usize::from(banner_area.width.saturating_sub(elapsed.len() as u16 + 20)),
+                )),
+            ]))
+            .render(banner_area, buf);
+            table_area
+        } else {
+            area
+        };
 
-fn ctrl_backspace_remaining<T: AsRef<str>>(s: T) -> String {
-    let s = String::from(s.as_ref());
-    let cut_pos = {
-        let mut chars = s.char_indices().rev();
+        let area = if let Some(ref input) = self.quick_add {
+            let [table_area, input_area] =
+                Layout::vertical([Constraint::Min(0), Constraint::Length(3)]).areas(area);
 
-        let mut pos_after_trailing_ws = None;
-        for (idx, ch) in &mut chars {
-            if !ch.is_whitespace() {
-                pos_after_trailing_ws = Some(idx + ch.len_utf8());
-                break;
-            }
-        }
+            let block = Block::bordered()
+                .title(Span::styled(
+                    format!(" {} ", t("Quick Add")),
+                    Style::default().bold().fg(COLOR_SECONDARY),
+                ))
+                .title_bottom(Line::from(vec![
+                    Span::raw(format!(" {} ", t("Add"))),
+                    Span::styled(
+                        "<Enter>",
+                        Style::default()
+                            .fg(COLOR_PRIMARY)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Span::raw(format!(" {} ", t("Cancel"))),
+                    Span::styled(
+                        "<CTRL-c> | <ESC>",
+                        Style::default()
+                            .fg(COLOR_PRIMARY)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                ]))
+                .title_style(Style::default().bold().fg(Color::White))
+                .border_style(Color::White);
 
-        if pos_after_trailing_ws.is_some() {
-            let mut o = 0;
-            for (idx, ch) in chars {
-                if ch.is_whitespace() {
-                    o = idx + ch.len_utf8();
-                    o = o.saturating_sub(1);
-                    break;
-                }
+            Paragraph::new(input.as_str())
+                .block(block)
+                .render(input_area, buf);
+
+            table_area
+        } else if let Some(ref input) = self.filter_input {
+            let [table_area, input_area] =
+                Layout::vertical([Constraint::Min(0), Constraint::Length(3)]).areas(area);
+
+            let mut title = Line::from(Span::styled(
+                format!(" {} ", t("Search")),
+                Style::default().bold().fg(COLOR_SECONDARY),
+            ));
+            if let Some(ref error) = self.filter_error {
+                title.push_span(Span::styled(
+                    format!("{error} "),
+                    Style::default().bold().fg(Color::LightRed),
+                ));
             }
-            o
+            let block = Block::bordered()
+                .title(title)
+                .title_bottom(key_hints(
+                    &[("Apply", "<Enter>"), ("Cancel", "<CTRL-c> | <ESC>")],
+                    COLOR_PRIMARY,
+                ))
+                .title_style(Style::default().bold().fg(Color::White))
+                .border_style(Color::White);
+
+            Paragraph::new(input.as_str())
+                .block(block)
+                .render(input_area, buf);
+
+            table_area
         } else {
-            0
+            area
+        };
+
+        StatefulWidget::render(table, area, buf, &mut self.state);
+
+        // the borders and the header row are not part of the scrollable rows
+        let fitting = usize::from(area.height.saturating_sub(3) / self.density.height());
+        if total > fitting {
+            // sized so the thumb covers the rows in view
+            let mut scrollbar = ScrollbarState::new(total - fitting + 1)
+                .position(self.state.offset())
+                .viewport_content_length(fitting);
+            StatefulWidget::render(
+                Scrollbar::new(ScrollbarOrientation::VerticalRight)
+                    .begin_symbol(None)
+                    .end_symbol(None)
+                    .thumb_style(Style::default().fg(primary_color))
+                    .track_style(Style::default().fg(COLOR_TERTIARY_DARK)),
+                area.inner(Margin::new(0, 1)),
+                buf,
+                &mut scrollbar,
+            );
         }
-    };
+    }
+}
 
-    String::from(&s[..cut_pos])
+/// `Ctrl-^`, which terminals commonly report as `Ctrl-6`, toggles the previous view like it
+/// toggles the alternate buffer in vim.
+fn is_alternate_key(key_event: KeyEvent) -> bool {
+    action::find(key_event, false) == Some(Action::PreviousView)
 }
 
-fn handle_backspace(item: Item, key_event: KeyEvent) -> Option<Item> {
-    let mut tmp = item;
-    let mut s: String = tmp.content();
-    if !s.is_empty() {
-        tmp.update(if key_event.modifiers.contains(KeyModifiers::CONTROL) {
-            ctrl_backspace_remaining(s)
-        } else {
-            s.truncate(s.len() - 1);
-            s
-        });
-        return Some(tmp);
-    }
-    None
+/// Starts a timestamped note on a new line, like `\n[14:32] `.
+fn note_prefix() -> String {
+    format!("\n[{}] ", chrono::Local::now().format("%H:%M"))
+}
+
+/// Renders `(label, keys)` pairs the way the bottom title of every block presents them.
+fn key_hints<'a>(hints: &[(&'a str, &'a str)], color: Color) -> Line<'a> {
+    Line::from(
+        hints
+            .iter()
+            .flat_map(|(label, keys)| {
+                [
+                    Span::raw(format!(" {} ", t(label))),
+                    Span::styled(
+                        *keys,
+                        Style::default().fg(color).add_modifier(Modifier::BOLD),
+                    ),
+                ]
+            })
+            .collect::<Vec<Span>>(),
+    )
+}
+
+/// The first of `hints` that fit into `width` columns as [`key_hints`] renders them, always
+/// with the last one, e.g. how to quit.
+fn fit_hints<'a>(hints: &[(&'a str, &'a str)], width: u16) -> Vec<(&'a str, &'a str)> {
+    let hint_width = |(label, keys): &(&str, &str)| t(label).width() + keys.width() + 2;
+    let Some((last, rest)) = hints.split_last() else {
+        return Vec::new();
+    };
+    let mut left = usize::from(width).saturating_sub(hint_width(last));
+    let mut fitting: Vec<_> = rest
+        .iter()
+        .take_while(|hint| {
+            let fits = hint_width(hint) <= left;
+            left = left.saturating_sub(hint_width(hint));
+            fits
+        })
+        .copied()
+        .collect();
+    fitting.push(*last);
+    fitting
+}
+
+fn draw_too_small(frame: &mut Frame) {
+    let area = frame.area();
+    let text = i18n::fill(
+        "The terminal is too small, lw needs at least {} columns and {} rows.",
+        &[
+            &popup::MIN_WIDTH.to_string(),
+            &popup::MIN_HEIGHT.to_string(),
+        ],
+    );
+    // one more line than the text needs on its own, for the words wrapped early
+    let lines = (text.width() as u16).div_ceil(area.width.max(1)) + 1;
+    let [area] = Layout::vertical([Constraint::Length(lines)])
+        .flex(Flex::Center)
+        .areas(area);
+    frame.render_widget(
+        Paragraph::new(text)
+            .style(Style::default().fg(COLOR_SECONDARY).bold())
+            .centered()
+            .wrap(ratatui::widgets::Wrap { trim: true }),
+        area,
+    );
 }
 
 fn popup_area(area: Rect, percent_x: u16, percent_y: u16) -> Rect {
@@ -526,3 +3550,36 @@ fn popup_area(area: Rect, percent_x: u16, percent_y: u16) -> Rect {
     let [area] = horizontal.areas(area);
     area
 }
+
+/// Draws `lines` in a box below the text at `anchor`, or above it when there is no room
+/// below, such as the completions of a tag.
+fn draw_popup(frame: &mut Frame, anchor: Position, lines: Vec<Line>, hint: &str) {
+    let width = lines
+        .iter()
+        .map(Line::width)
+        .chain([hint.width()])
+        .max()
+        .unwrap_or(0) as u16
+        + 2;
+    let height = lines.len() as u16 + 2;
+    let screen = frame.area();
+    let x = anchor.x.min(screen.right().saturating_sub(width));
+    let y = if anchor.y + 1 + height <= screen.bottom() {
+        anchor.y + 1
+    } else {
+        anchor.y.saturating_sub(height)
+    };
+    let area = Rect::new(x, y, width, height).intersection(screen);
+    frame.render_widget(Clear, area);
+    frame.render_widget(
+        Paragraph::new(lines).block(
+            Block::bordered()
+                .title_bottom(Span::styled(
+                    hint,
+                    Style::default().fg(COLOR_PRIMARY).bold(),
+                ))
+                .border_style(Style::default().fg(COLOR_TERTIARY_DARK)),
+        ),
+        area,
+    );
+}
@@ -1,13 +1,40 @@
-use chrono::{DateTime, Local};
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Datelike, Local, TimeDelta};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::attachment::Attachment;
+use crate::{collate, i18n, tag};
+
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Item {
     id: Uuid,
     content: String,
     created: DateTime<Local>,
     modified: DateTime<Local>,
+    #[serde(default)]
+    pinned: bool,
+    /// Whether the time tracked on this entry is billed, see `lw invoice`.
+    #[serde(default)]
+    billable: bool,
+    /// Entries this one follows up on or refers to.
+    #[serde(default)]
+    links: Vec<Uuid>,
+    /// Seconds spent on this entry while it was marked as current.
+    #[serde(default)]
+    tracked: u64,
+    #[serde(default)]
+    attachments: Vec<Attachment>,
+    /// Values of the custom fields declared in the settings.
+    #[serde(default)]
+    extra: BTreeMap<String, String>,
+    /// The daily note of the day it was created on, see `lw today`.
+    #[serde(default)]
+    daily: bool,
+    /// Checked off, e.g. in the review of a day.
+    #[serde(default)]
+    done: bool,
 }
 
 impl Item {
@@ -15,6 +42,24 @@ impl Item {
         Self::default()
     }
 
+    /// A daily note for today, headed by the date.
+    pub fn daily_note() -> Self {
+        let today = Local::now().date_naive();
+        Self {
+            content: format!(
+                "{}, {}\n",
+                i18n::weekday(today.weekday()),
+                i18n::long_date(today)
+            ),
+            daily: true,
+            ..Self::default()
+        }
+    }
+
+    pub fn daily(&self) -> bool {
+        self.daily
+    }
+
     pub fn modified(&self) -> DateTime<Local> {
         self.modified
     }
@@ -26,14 +71,164 @@ impl Item {
         self.content.clone()
     }
 
+    /// Moves the creation time to `at`, for entries logged after the fact.
+    pub fn backdate(&mut self, at: DateTime<Local>) {
+        self.created = at;
+        self.modified = at;
+    }
+
     pub fn update(&mut self, content: String) {
         self.content = content;
         self.modified = Local::now();
     }
 
+    pub fn tags(&self) -> Vec<String> {
+        tag::markers(&self.content, tag::TAG)
+    }
+
+    pub fn projects(&self) -> Vec<String> {
+        tag::markers(&self.content, tag::PROJECT)
+    }
+
+    /// People mentioned as `@name`.
+    pub fn people(&self) -> Vec<String> {
+        tag::markers(&self.content, tag::PERSON)
+    }
+
+    /// Whether `person`, with or without the leading `@`, is mentioned, ignoring case and
+    /// accents.
+    pub fn mentions(&self, person: &str) -> bool {
+        let person = collate::key(person.strip_prefix(tag::PERSON).unwrap_or(person));
+        self.people().iter().any(|p| collate::key(p) == person)
+    }
+
+    pub fn pinned(&self) -> bool {
+        self.pinned
+    }
+
+    pub fn toggle_pin(&mut self) {
+        self.pinned = !self.pinned;
+    }
+
+    pub fn billable(&self) -> bool {
+        self.billable
+    }
+
+    pub fn toggle_billable(&mut self) {
+        self.billable = !self.billable;
+        self.modified = Local::now();
+    }
+
+    pub fn done(&self) -> bool {
+        self.done
+    }
+
+    pub fn toggle_done(&mut self) {
+        self.done = !self.done;
+        self.modified = Local::now();
+    }
+
+    pub fn tracked(&self) -> TimeDelta {
+        i64::try_from(self.tracked)
+            .ok()
+            .and_then(TimeDelta::try_seconds)
+            .unwrap_or(TimeDelta::MAX)
+    }
+
+    /// Adds `duration` to the time spent on this entry.
+    pub fn track(&mut self, duration: TimeDelta) {
+        let seconds = u64::try_from(duration.num_seconds()).unwrap_or(0);
+        self.tracked = self.tracked.saturating_add(seconds);
+        self.modified = Local::now();
+    }
+
+    pub fn links(&self) -> Vec<String> {
+        self.links.iter().map(Uuid::to_string).collect()
+    }
+
+    pub fn links_to(&self, other: &Item) -> bool {
+        self.links.contains(&other.id)
+    }
+
+    pub fn link(&mut self, other: &Item) {
+        if other.id != self.id && !self.links_to(other) {
+            self.links.push(other.id);
+        }
+    }
+
+    pub fn attachments(&self) -> &[Attachment] {
+        &self.attachments
+    }
+
+    pub fn attach(&mut self, attachment: Attachment) {
+        if !self.attachments.contains(&attachment) {
+            self.attachments.push(attachment);
+            self.modified = Local::now();
+        }
+    }
+
+    pub fn field(&self, name: &str) -> Option<&str> {
+        self.extra.get(name).map(String::as_str)
+    }
+
+    pub fn fields(&self) -> &BTreeMap<String, String> {
+        &self.extra
+    }
+
+    /// Sets the custom field `name`, removing it when `value` is empty.
+    pub fn set_field(&mut self, name: &str, value: String) {
+        let previous = if value.is_empty() {
+            self.extra.remove(name)
+        } else {
+            self.extra.insert(name.to_owned(), value.clone())
+        };
+        if previous.unwrap_or_default() != value {
+            self.modified = Local::now();
+        }
+    }
+
+    /// Replaces all custom fields with `fields`.
+    pub fn set_fields(&mut self, fields: &BTreeMap<String, String>) {
+        if self.extra != *fields {
+            self.extra = fields.clone();
+            self.modified = Local::now();
+        }
+    }
+
+    /// Compact icons describing the metadata attached to this entry.
+    pub fn badges(&self) -> String {
+        let mut badges = String::new();
+        if self.daily {
+            badges.push('📓');
+        }
+        if self.pinned {
+            badges.push('📌');
+        }
+        if !self.links.is_empty() {
+            badges.push('🔗');
+        }
+        if self.tracked > 0 {
+            badges.push('⏱');
+        }
+        if !self.attachments.is_empty() {
+            badges.push('📎');
+        }
+        if self.billable {
+            badges.push('💰');
+        }
+        if self.done {
+            badges.push('✅');
+        }
+        badges
+    }
+
     pub fn id(&self) -> String {
         self.id.to_string()
     }
+
+    pub fn uuid(&self) -> Uuid {
+        self.id
+    }
 }
 
 impl Default for Item {
@@ -44,6 +239,14 @@ impl Default for Item {
             content: String::new(),
             created: now,
             modified: now,
+            pinned: false,
+            billable: false,
+            links: Vec::new(),
+            tracked: 0,
+            attachments: Vec::new(),
+            extra: BTreeMap::new(),
+            daily: false,
+            done: false,
         }
     }
 }
@@ -1,30 +1,54 @@
 use std::env;
 
 use color_eyre::Result;
-use lw::App;
+use lw::cli::{self, Command};
+use lw::settings::Settings;
+use lw::{App, daemon, recover};
 
 fn main() -> Result<()> {
     color_eyre::install()?;
 
-    let mut app = App::default();
-
-    let args = env::args();
-
-    if args.len() > 1 {
-        app.add(
-            args.into_iter()
-                .skip(1)
-                .collect::<Vec<String>>()
-                .join(" ")
-                .into(),
-        ); //.for_each(|a| app.add(a.into()));
-        app.save()?;
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    let safe_mode = args.first().is_some_and(|arg| arg == "--safe-mode");
+    // a running daemon has the log loaded already
+    if !safe_mode && daemon::forward(&args)? {
         return Ok(());
     }
+    if safe_mode {
+        args.remove(0);
+    }
+    // relative dates in the arguments, e.g. `--since "this week"`, are read in the locale of
+    // the settings; a failure to load them is reported by `App::new`
+    if !safe_mode && let Ok(settings) = Settings::load() {
+        settings.apply_locale();
+    }
+    let command = Command::parse(args)?;
+    // shows where the files would be, so it must not create them
+    if matches!(command, Command::Paths) {
+        cli::paths();
+        return Ok(());
+    }
+    let tui = matches!(command, Command::Tui);
+    let mut app = if safe_mode {
+        App::safe_mode(App::config_path().to_owned())?
+    } else {
+        match App::new(App::config_path().to_owned()) {
+            Ok(app) => app,
+            // the TUI offers to retry or to set a corrupt file aside
+            Err(e) if tui => match recover::run(App::config_path(), e)? {
+                Some(app) => app,
+                None => return Ok(()),
+            },
+            Err(e) => return Err(e),
+        }
+    };
 
-    let terminal = ratatui::init();
-
-    let result = app.run(terminal);
-    ratatui::restore();
+    let result = command.run(&mut app);
+    // the TUI shows these in toasts
+    if !tui {
+        for error in app.take_errors() {
+            eprintln!("warning: {error}");
+        }
+    }
     result
 }
@@ -0,0 +1,80 @@
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+use crate::log::Item;
+use crate::undo::Change;
+
+/// Marks an entry as deleted, so merging in an older copy of the log doesn't bring it back.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Tombstone {
+    pub id: String,
+    pub deleted: DateTime<Local>,
+}
+
+/// Outcome of merging another copy of the log into the local one.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct MergeReport {
+    pub added: usize,
+    pub updated: usize,
+    pub removed: usize,
+}
+
+/// Unions `other` into `local` by item id. When both sides know an item, the more recently
+/// modified version wins. Items deleted on either side stay deleted unless they were modified
+/// after the deletion.
+///
+/// Returns the report and the previous state of every local item that was replaced or removed.
+pub fn merge(
+    local: &mut Vec<Item>,
+    tombstones: &mut Vec<Tombstone>,
+    other: Vec<Item>,
+    other_tombstones: Vec<Tombstone>,
+) -> (MergeReport, Vec<Change>) {
+    let mut report = MergeReport::default();
+    let mut changes = Vec::new();
+
+    for tombstone in other_tombstones {
+        match tombstones.iter_mut().find(|t| t.id == tombstone.id) {
+            Some(existing) => existing.deleted = existing.deleted.max(tombstone.deleted),
+            None => tombstones.push(tombstone),
+        }
+    }
+
+    let deleted = |item: &Item, tombstones: &[Tombstone]| {
+        tombstones
+            .iter()
+            .any(|t| t.id == item.id() && t.deleted >= item.modified())
+    };
+
+    for item in other {
+        if deleted(&item, tombstones) {
+            continue;
+        }
+        match local.iter_mut().find(|i| i.id() == item.id()) {
+            Some(existing) => {
+                if item.modified() > existing.modified() {
+                    changes.push(Change::Updated(existing.clone()));
+                    *existing = item;
+                    report.updated += 1;
+                }
+            }
+            None => {
+                local.push(item);
+                report.added += 1;
+            }
+        }
+    }
+
+    local.retain(|item| {
+        if deleted(item, tombstones) {
+            changes.push(Change::Removed(item.clone()));
+            report.removed += 1;
+            return false;
+        }
+        true
+    });
+    // entries edited after their deletion were deliberately kept, forget the deletion
+    tombstones.retain(|t| !local.iter().any(|i| i.id() == t.id));
+
+    (report, changes)
+}
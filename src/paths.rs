@@ -0,0 +1,98 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use color_eyre::{Result, eyre::eyre};
+
+use crate::APP_NAME;
+
+/// Environment variable naming the directory to keep every file of lw in, overriding the
+/// platform's default.
+pub static OVERRIDE: &str = "LW_CONFIG_DIR";
+
+/// Why the directory of lw is where it is, shown by `lw paths`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Source {
+    /// Set with [`OVERRIDE`].
+    Override,
+    /// `$XDG_CONFIG_HOME` on Linux and other Unixes.
+    Xdg,
+    /// `~/.config`, where lw used to keep its files on macOS and regardless of
+    /// `$XDG_CONFIG_HOME`, found with a log in it.
+    Legacy,
+    /// The configuration directory of the platform.
+    Platform,
+    /// A temporary directory, since neither the home nor the profile directory is known.
+    Fallback,
+}
+
+impl Source {
+    pub fn describe(self) -> &'static str {
+        match self {
+            Self::Override => "from LW_CONFIG_DIR",
+            Self::Xdg => "from XDG_CONFIG_HOME",
+            Self::Legacy => "kept from an earlier version",
+            Self::Platform => "platform default",
+            Self::Fallback => "no home directory found",
+        }
+    }
+}
+
+/// A variable that is set to something, as tools commonly treat empty ones as unset.
+fn var(name: &str) -> Option<PathBuf> {
+    env::var_os(name)
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from)
+}
+
+/// The directory holding the log, its settings and everything else lw writes: `%APPDATA%\lw`
+/// on Windows, `~/Library/Application Support/lw` on macOS and `$XDG_CONFIG_HOME/lw` or
+/// `~/.config/lw` elsewhere. Nothing is created, see [`create`].
+pub fn config_dir() -> (PathBuf, Source) {
+    if let Some(dir) = var(OVERRIDE) {
+        return (dir, Source::Override);
+    }
+    let home = var(if cfg!(windows) { "USERPROFILE" } else { "HOME" });
+    if cfg!(windows) {
+        if let Some(base) = var("APPDATA") {
+            return (base.join(APP_NAME), Source::Platform);
+        }
+        if let Some(home) = home {
+            let base = home.join("AppData").join("Roaming");
+            return (base.join(APP_NAME), Source::Platform);
+        }
+    } else if cfg!(target_os = "macos") {
+        if let Some(home) = home {
+            // logs started before lw followed the platform stay where they are
+            let legacy = home.join(".config").join(APP_NAME);
+            if legacy.join("config.json").exists() {
+                return (legacy, Source::Legacy);
+            }
+            let base = home.join("Library").join("Application Support");
+            return (base.join(APP_NAME), Source::Platform);
+        }
+    } else {
+        if let Some(base) = var("XDG_CONFIG_HOME").filter(|base| base.is_absolute()) {
+            let dir = base.join(APP_NAME);
+            // logs started before lw read XDG_CONFIG_HOME stay where they are, unless there
+            // is one in the new place already
+            if let Some(legacy) = home.map(|home| home.join(".config").join(APP_NAME))
+                && legacy != dir
+                && legacy.join("config.json").exists()
+                && !dir.join("config.json").exists()
+            {
+                return (legacy, Source::Legacy);
+            }
+            return (dir, Source::Xdg);
+        }
+        if let Some(home) = home {
+            return (home.join(".config").join(APP_NAME), Source::Platform);
+        }
+    }
+    (env::temp_dir().join(APP_NAME), Source::Fallback)
+}
+
+/// Creates `dir` and its parents unless they exist.
+pub fn create(dir: &Path) -> Result<()> {
+    fs::create_dir_all(dir).map_err(|e| eyre!("failed to create {}: {e}", dir.display()))
+}
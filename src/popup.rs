@@ -0,0 +1,88 @@
+use std::{fs, path::PathBuf};
+
+use color_eyre::Result;
+use ratatui::layout::{Constraint, Flex, Layout, Rect};
+use serde::{Deserialize, Serialize};
+
+use crate::{App, storage};
+
+/// Smallest terminal the TUI is drawn in, smaller ones only get asked to grow.
+pub const MIN_WIDTH: u16 = 40;
+pub const MIN_HEIGHT: u16 = 10;
+/// Terminals up to this size get the editor on the whole screen.
+static SMALL_WIDTH: u16 = 60;
+static SMALL_HEIGHT: u16 = 16;
+/// Percent the editor grows or shrinks by per key press.
+static STEP: i16 = 5;
+static MIN_PERCENT: i16 = 30;
+
+/// Size of the editor in percent of the terminal, set with `<ALT-arrows>` and remembered
+/// across runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Size {
+    pub width: u16,
+    pub height: u16,
+}
+
+impl Default for Size {
+    fn default() -> Self {
+        Self {
+            width: 90,
+            height: 90,
+        }
+    }
+}
+
+impl Size {
+    /// Grows the size by `width` and `height` steps, shrinking it for negative ones.
+    pub fn resize(self, width: i16, height: i16) -> Self {
+        let step = |percent: u16, steps: i16| {
+            (percent as i16 + steps * STEP).clamp(MIN_PERCENT, 100) as u16
+        };
+        Self {
+            width: step(self.width, width),
+            height: step(self.height, height),
+        }
+    }
+
+    /// Where the editor goes on `screen`: centered, or all of a small screen.
+    pub fn area(self, screen: Rect) -> Rect {
+        if screen.width <= SMALL_WIDTH || screen.height <= SMALL_HEIGHT {
+            return screen;
+        }
+        let [area] = Layout::vertical([Constraint::Percentage(self.height)])
+            .flex(Flex::Center)
+            .areas(screen);
+        let [area] = Layout::horizontal([Constraint::Percentage(self.width)])
+            .flex(Flex::Center)
+            .areas(area);
+        area
+    }
+}
+
+/// Whether `screen` is too small to draw the TUI in.
+pub fn too_small(screen: Rect) -> bool {
+    screen.width < MIN_WIDTH || screen.height < MIN_HEIGHT
+}
+
+pub fn path() -> PathBuf {
+    App::config_path().with_file_name("popup.json")
+}
+
+/// The remembered size, if it was ever changed.
+pub fn load() -> Option<Size> {
+    let content = fs::read_to_string(path()).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Remembers `size`, or forgets the remembered one for the default.
+pub fn save(size: Size) -> Result<()> {
+    let path = path();
+    if size == Size::default() {
+        if path.exists() {
+            fs::remove_file(path)?;
+        }
+        return Ok(());
+    }
+    storage::replace(&path, serde_json::to_string_pretty(&size)?.as_bytes())
+}
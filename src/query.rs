@@ -0,0 +1,280 @@
+use std::fmt;
+use std::str::FromStr;
+
+use std::collections::HashSet;
+
+use chrono::{Local, NaiveDate};
+use color_eyre::{Report, Result, eyre::eyre};
+use regex::{Regex, RegexBuilder};
+use uuid::Uuid;
+
+use crate::index::{Entry, Index};
+use crate::log::Item;
+use crate::settings::FieldType;
+use crate::{collate, dates, tag};
+
+/// A search such as `tag:foo project:acme /deploy.*prod/ before:2024-06-01 field:ticket=ABC-1`.
+/// Every term has to match for an entry to be included.
+#[derive(Debug, Clone)]
+pub struct Query {
+    source: String,
+    terms: Vec<Term>,
+}
+
+/// Text and marker names are stored as collation keys, see [`collate::key`].
+#[derive(Debug, Clone)]
+enum Term {
+    /// Words anywhere in the content, ignoring case and accents.
+    Text(String),
+    Tag(String),
+    Project(String),
+    Person(String),
+    /// Case-insensitive regular expression, written as `/pattern/`.
+    Regex(Regex),
+    /// Created before the start of the day.
+    Before(NaiveDate),
+    /// Created on or after the day.
+    After(NaiveDate),
+    On(NaiveDate),
+    Pinned,
+    Billable,
+    Done,
+    /// A custom field, written as `field:name` to require a value or `field:name=value`.
+    Field(String, Option<String>),
+    Not(Box<Term>),
+}
+
+impl Query {
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    pub fn matches(&self, item: &Item) -> bool {
+        self.matches_entry(item, &Entry::new(item))
+    }
+
+    fn matches_entry(&self, item: &Item, entry: &Entry) -> bool {
+        self.terms.iter().all(|term| term.matches(item, entry))
+    }
+
+    /// Indices into `logs` of the matching entries, using `index` to skip entries without the
+    /// required tags, projects or people and to avoid folding every entry again.
+    pub fn filter(&self, logs: &[Item], index: &Index) -> Vec<usize> {
+        let mut required: Vec<&HashSet<Uuid>> = Vec::new();
+        for (prefix, name) in self.terms.iter().filter_map(Term::marker) {
+            match index.tagged(prefix, name) {
+                Some(ids) => required.push(ids),
+                None => return Vec::new(),
+            }
+        }
+        logs.iter()
+            .enumerate()
+            .filter(|(_, item)| {
+                let id = item.uuid();
+                required.iter().all(|ids| ids.contains(&id))
+                    && match index.get(id) {
+                        Some(entry) => self.matches_entry(item, entry),
+                        None => self.matches(item),
+                    }
+            })
+            .map(|(i, _)| i)
+            .collect()
+    }
+}
+
+impl Term {
+    fn parse(token: &str) -> Result<Self> {
+        if let Some(negated) = token.strip_prefix('-')
+            && !negated.is_empty()
+        {
+            return Ok(Self::Not(Box::new(Self::parse(negated)?)));
+        }
+        if let Some(pattern) = token
+            .strip_prefix('/')
+            .and_then(|t| t.strip_suffix('/'))
+            .filter(|p| !p.is_empty())
+        {
+            return RegexBuilder::new(pattern)
+                .case_insensitive(true)
+                .build()
+                .map(Self::Regex)
+                .map_err(|e| eyre!("invalid regular expression /{pattern}/: {e}"));
+        }
+        if let Some(phrase) = token.strip_prefix('"') {
+            return Ok(Self::Text(collate::key(phrase.trim_end_matches('"'))));
+        }
+        if let Some((field, value)) = token.split_once(':')
+            && !value.is_empty()
+        {
+            let value = value.trim_matches('"');
+            match field {
+                "tag" => return Ok(Self::Tag(collate::key(value))),
+                "project" => return Ok(Self::Project(collate::key(value))),
+                "person" | "with" => {
+                    return Ok(Self::Person(collate::key(
+                        value.trim_start_matches(tag::PERSON),
+                    )));
+                }
+                "before" => return Ok(Self::Before(date(value)?)),
+                "after" => return Ok(Self::After(date(value)?)),
+                "on" => return Ok(Self::On(date(value)?)),
+                "is" if value == "pinned" => return Ok(Self::Pinned),
+                "is" if value == "billable" => return Ok(Self::Billable),
+                "is" if value == "done" => return Ok(Self::Done),
+                "is" => {
+                    return Err(eyre!(
+                        "unknown filter is:{value}, expected is:pinned, is:billable or is:done"
+                    ));
+                }
+                "field" => {
+                    return Ok(match value.split_once('=') {
+                        Some((name, value)) => {
+                            Self::Field(name.to_owned(), Some(collate::key(value)))
+                        }
+                        None => Self::Field(value.to_owned(), None),
+                    });
+                }
+                _ => {}
+            }
+        }
+        for (prefix, term) in [
+            (tag::TAG, Self::Tag as fn(String) -> Self),
+            (tag::PROJECT, Self::Project),
+            (tag::PERSON, Self::Person),
+        ] {
+            if let Some(name) = token.strip_prefix(prefix)
+                && !name.is_empty()
+            {
+                return Ok(term(collate::key(name)));
+            }
+        }
+        Ok(Self::Text(collate::key(token)))
+    }
+
+    /// The marker an entry is required to carry for this term to match.
+    fn marker(&self) -> Option<(char, &str)> {
+        match self {
+            Self::Tag(name) => Some((tag::TAG, name)),
+            Self::Project(name) => Some((tag::PROJECT, name)),
+            Self::Person(name) => Some((tag::PERSON, name)),
+            _ => None,
+        }
+    }
+
+    fn matches(&self, item: &Item, entry: &Entry) -> bool {
+        let day = item.created().date_naive();
+        match self {
+            Self::Text(text) => entry.text.contains(text.as_str()),
+            Self::Tag(name) => entry.tags.contains(name),
+            Self::Project(name) => entry.projects.contains(name),
+            Self::Person(name) => entry.people.contains(name),
+            Self::Regex(regex) => regex.is_match(&item.content()),
+            Self::Before(date) => day < *date,
+            Self::After(date) => day >= *date,
+            Self::On(date) => day == *date,
+            Self::Pinned => item.pinned(),
+            Self::Billable => item.billable(),
+            Self::Done => item.done(),
+            Self::Field(name, value) => item.field(name).is_some_and(|v| {
+                // yes and no also match booleans, which are stored as true and false
+                value.as_ref().is_none_or(|value| {
+                    collate::key(v) == *value || FieldType::Bool.parse(value).is_ok_and(|b| b == v)
+                })
+            }),
+            Self::Not(term) => !term.matches(item, entry),
+        }
+    }
+}
+
+impl FromStr for Query {
+    type Err = Report;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let terms = tokenize(s)?
+            .iter()
+            .map(|token| Term::parse(token))
+            .collect::<Result<_>>()?;
+        Ok(Self {
+            source: s.trim().to_owned(),
+            terms,
+        })
+    }
+}
+
+impl PartialEq for Query {
+    fn eq(&self, other: &Self) -> bool {
+        self.source == other.source
+    }
+}
+
+impl Eq for Query {}
+
+impl fmt::Display for Query {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+fn date(value: &str) -> Result<NaiveDate> {
+    dates::day(value, Local::now().date_naive())
+}
+
+/// Splits `s` at whitespace, keeping `/regular expressions/`, `"quoted phrases"` and quoted
+/// values like `before:"2 weeks ago"` together.
+fn tokenize(s: &str) -> Result<Vec<String>> {
+    let mut tokens = Vec::new();
+    let mut chars = s.trim().chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+        let mut token = String::new();
+        let negated = c == '-';
+        if negated {
+            token.push(c);
+            chars.next();
+        }
+        match chars.peek() {
+            Some(&delimiter @ ('/' | '"')) => {
+                token.push(delimiter);
+                chars.next();
+                let mut closed = false;
+                while let Some(c) = chars.next() {
+                    if c == '\\' && delimiter == '/' && chars.peek() == Some(&'/') {
+                        token.push('/');
+                        chars.next();
+                        continue;
+                    }
+                    token.push(c);
+                    if c == delimiter {
+                        closed = true;
+                        break;
+                    }
+                }
+                if !closed {
+                    return Err(eyre!("unterminated {delimiter} in query"));
+                }
+            }
+            _ => {
+                let mut quoted = false;
+                while let Some(&c) = chars.peek() {
+                    if c.is_whitespace() && !quoted {
+                        break;
+                    }
+                    // a quoted value keeps its spaces, e.g. before:"2 weeks ago"
+                    if c == '"' {
+                        quoted = !quoted;
+                    }
+                    token.push(c);
+                    chars.next();
+                }
+                if quoted {
+                    return Err(eyre!("unterminated \" in query"));
+                }
+            }
+        }
+        tokens.push(token);
+    }
+    Ok(tokens)
+}
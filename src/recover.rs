@@ -0,0 +1,156 @@
+use std::path::{Path, PathBuf};
+
+use color_eyre::{Report, Result};
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Clear, Paragraph, Wrap};
+use ratatui::{DefaultTerminal, style::Color};
+
+use crate::i18n::{self, Language, t};
+use crate::settings::Settings;
+use crate::storage::{self, Corrupt};
+use crate::{App, COLOR_PRIMARY, COLOR_SECONDARY};
+
+/// The file behind `error` if it failed because the file is corrupt.
+pub fn corrupt_file(error: &Report) -> Option<&Path> {
+    error
+        .downcast_ref::<Corrupt>()
+        .map(|corrupt| corrupt.path.as_path())
+}
+
+/// Shows why the log failed to load and lets the user retry, e.g. after fixing the settings
+/// in another terminal, or set a corrupt file aside. Returns `None` if they gave up.
+pub fn run(config: &Path, error: Report) -> Result<Option<App>> {
+    // the settings may be what failed to load
+    Settings::load()
+        .ok()
+        .and_then(|settings| settings.language)
+        .unwrap_or_else(Language::detect)
+        .set();
+    let mut terminal = ratatui::init();
+    let result = recover(&mut terminal, config, error);
+    ratatui::restore();
+    result
+}
+
+fn recover(
+    terminal: &mut DefaultTerminal,
+    config: &Path,
+    mut error: Report,
+) -> Result<Option<App>> {
+    let mut moved: Option<PathBuf> = None;
+    loop {
+        terminal.draw(|frame| draw(frame, &error, moved.as_deref()))?;
+        let Event::Key(key_event) = event::read()? else {
+            continue;
+        };
+        if key_event.kind != KeyEventKind::Press {
+            continue;
+        }
+        match key_event.code {
+            KeyCode::Char('r') | KeyCode::Enter => {}
+            KeyCode::Char('b') => {
+                let Some(path) = corrupt_file(&error).map(Path::to_owned) else {
+                    continue;
+                };
+                match storage::set_aside(&path) {
+                    Ok(target) => {
+                        moved = Some(target);
+                        if path == config
+                            && let Err(e) = App::init(config)
+                        {
+                            error = e;
+                            continue;
+                        }
+                    }
+                    Err(e) => {
+                        error = e;
+                        continue;
+                    }
+                }
+            }
+            KeyCode::Char('q') | KeyCode::Esc => return Ok(None),
+            _ => continue,
+        }
+        match App::new(config.to_owned()) {
+            Ok(mut app) => {
+                if let Some(moved) = moved {
+                    app.notify(i18n::fill(
+                        "The damaged file was moved to {}.",
+                        &[&moved.display().to_string()],
+                    ));
+                }
+                return Ok(Some(app));
+            }
+            Err(e) => error = e,
+        }
+    }
+}
+
+fn draw(frame: &mut Frame, error: &Report, moved: Option<&Path>) {
+    let corrupt = corrupt_file(error);
+    let mut hints = vec![
+        Span::raw(format!(" {} ", t("Retry"))),
+        keys("<r> | <Enter>"),
+    ];
+    if corrupt.is_some() {
+        hints.push(Span::raw(format!(" {} ", t("Set aside"))));
+        hints.push(keys("<b>"));
+    }
+    hints.push(Span::raw(format!(" {} ", t("Quit"))));
+    hints.push(keys("<q> | <ESC>"));
+
+    let block = Block::bordered()
+        .title(Span::styled(
+            t("The log could not be loaded"),
+            Style::default().bold().fg(Color::LightRed),
+        ))
+        .title_bottom(Line::from(hints))
+        .title_style(Style::default().bold().fg(Color::White));
+
+    let mut lines = vec![Line::from(error.to_string()), Line::from("")];
+    if let Some(path) = corrupt {
+        lines.push(Line::from(Span::styled(
+            i18n::fill(
+                "{} is damaged. Set it aside to start without it, it is kept next to the original for recovery.",
+                &[&path.display().to_string()],
+            ),
+            Style::default().fg(COLOR_SECONDARY).bold(),
+        )));
+    } else {
+        lines.push(Line::from(Span::styled(
+            t("Fix the problem and retry, your data has not been changed."),
+            Style::default().fg(COLOR_SECONDARY).bold(),
+        )));
+    }
+    if let Some(moved) = moved {
+        lines.push(Line::from(""));
+        lines.push(Line::from(i18n::fill(
+            "The damaged file was moved to {}.",
+            &[&moved.display().to_string()],
+        )));
+    }
+
+    let [area] = Layout::vertical([Constraint::Percentage(50)])
+        .flex(layout::Flex::Center)
+        .areas(frame.area());
+    let [area] = Layout::horizontal([Constraint::Percentage(70)])
+        .flex(layout::Flex::Center)
+        .areas(area);
+    frame.render_widget(Clear, area);
+    frame.render_widget(
+        Paragraph::new(lines)
+            .block(block)
+            .wrap(Wrap { trim: false }),
+        area,
+    );
+}
+
+fn keys(keys: &str) -> Span<'_> {
+    Span::styled(
+        keys,
+        Style::default()
+            .fg(COLOR_PRIMARY)
+            .add_modifier(Modifier::BOLD),
+    )
+}
@@ -0,0 +1,130 @@
+use std::fmt::Write;
+use std::{fs, path::PathBuf};
+
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta};
+use color_eyre::Result;
+
+use crate::log::Item;
+use crate::{App, editor, focus, i18n, storage};
+
+/// Going through the entries of a day one by one, to fix their wording, add the time spent
+/// on them and mark them as done.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Review {
+    pub day: NaiveDate,
+    /// The entries of the day, oldest first.
+    pub ids: Vec<String>,
+    pub index: usize,
+    /// Time being typed to add to the entry, e.g. `1h30m`.
+    pub duration: Option<String>,
+    pub error: Option<String>,
+}
+
+impl Review {
+    /// The review of the entries created on `day`, `None` if there are none.
+    pub fn new(logs: &[Item], day: NaiveDate) -> Option<Self> {
+        let mut items: Vec<&Item> = logs
+            .iter()
+            .filter(|item| item.created().date_naive() == day)
+            .collect();
+        items.sort_by_key(|item| item.created());
+        if items.is_empty() {
+            return None;
+        }
+        Some(Self {
+            day,
+            ids: items.iter().map(|item| item.id()).collect(),
+            index: 0,
+            duration: None,
+            error: None,
+        })
+    }
+
+    pub fn id(&self) -> &str {
+        &self.ids[self.index]
+    }
+}
+
+/// A duration like `45m`, `1h`, `1h30m`, `1:30` or `90`, which counts minutes.
+pub fn parse_duration(s: &str) -> Option<TimeDelta> {
+    minutes(&s.trim().to_lowercase()).filter(|duration| *duration > TimeDelta::zero())
+}
+
+fn minutes(s: &str) -> Option<TimeDelta> {
+    if let Some((hours, minutes)) = s.split_once(':') {
+        let minutes: i64 = minutes.parse().ok().filter(|m| *m < 60)?;
+        return TimeDelta::try_hours(hours.parse().ok()?)?
+            .checked_add(&TimeDelta::try_minutes(minutes)?);
+    }
+    if let Ok(minutes) = s.parse() {
+        return TimeDelta::try_minutes(minutes);
+    }
+    let (hours, rest) = match s.split_once('h') {
+        Some((hours, rest)) => (hours.trim().parse().ok()?, rest.trim()),
+        None => (0, s),
+    };
+    let minutes = match rest.strip_suffix('m').or(rest.strip_suffix("min")) {
+        Some(minutes) => minutes.trim().parse().ok()?,
+        None if rest.is_empty() => 0,
+        // the minutes of `1h30`
+        None if s.contains('h') => rest.parse().ok()?,
+        None => return None,
+    };
+    TimeDelta::try_hours(hours)?.checked_add(&TimeDelta::try_minutes(minutes)?)
+}
+
+/// The entries of `day` as a Markdown checklist, with their time, first line and tracked
+/// time, followed by the total time.
+pub fn summary(logs: &[Item], day: NaiveDate) -> String {
+    let mut items: Vec<&Item> = logs
+        .iter()
+        .filter(|item| item.created().date_naive() == day)
+        .collect();
+    items.sort_by_key(|item| item.created());
+    let mut summary = format!(
+        "{}, {}\n\n",
+        i18n::weekday(day.weekday()),
+        i18n::long_date(day)
+    );
+    let mut total = TimeDelta::zero();
+    for item in items {
+        let content = item.content();
+        let (line, _) = editor::first_line(&content);
+        let _ = write!(
+            summary,
+            "- [{}] {} {}",
+            if item.done() { 'x' } else { ' ' },
+            item.created().format("%H:%M"),
+            line.trim()
+        );
+        if item.tracked() > TimeDelta::zero() {
+            let _ = write!(summary, " ({})", focus::format(item.tracked()));
+        }
+        summary.push('\n');
+        total = total.checked_add(&item.tracked()).unwrap_or(TimeDelta::MAX);
+    }
+    let _ = writeln!(
+        summary,
+        "\n{}",
+        i18n::fill("Tracked: {}", &[&focus::format(total)])
+    );
+    summary
+}
+
+pub fn path() -> PathBuf {
+    App::config_path().with_file_name("review.json")
+}
+
+/// Whether the review opens on starting at `now`: it is `after` the configured time and it
+/// did not open on an earlier start that day.
+pub fn due(after: NaiveTime, now: NaiveDateTime) -> bool {
+    let last: Option<NaiveDate> = fs::read_to_string(path())
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok());
+    now.time() >= after && last.is_none_or(|last| last < now.date())
+}
+
+/// Remembers that the review opened on `day`.
+pub fn opened(day: NaiveDate) -> Result<()> {
+    storage::replace(&path(), serde_json::to_string(&day)?.as_bytes())
+}
@@ -0,0 +1,80 @@
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+
+use chrono::{DateTime, Local};
+use uuid::Uuid;
+
+use crate::log::Item;
+use crate::settings::PreviewMode;
+use crate::tag;
+
+static TIMESTAMP: &str = "%Y-%m-%d %H:%M:%S";
+
+/// The text of a table row, made once per version of its entry instead of on every frame.
+#[derive(Debug, Clone)]
+pub struct Cells {
+    /// `created` and `modified` of the version the text was made from.
+    version: (DateTime<Local>, DateTime<Local>),
+    /// The preview before it is truncated to the column.
+    pub preview: String,
+    /// Projects, tags and people with their prefix, in the order of the chips.
+    pub markers: Vec<String>,
+    pub created: String,
+    pub modified: String,
+}
+
+impl Cells {
+    fn new(item: &Item, preview: String) -> Self {
+        let markers = item
+            .projects()
+            .into_iter()
+            .map(|p| format!("{}{p}", tag::PROJECT))
+            .chain(item.tags().into_iter().map(|t| format!("{}{t}", tag::TAG)))
+            .chain(
+                item.people()
+                    .into_iter()
+                    .map(|p| format!("{}{p}", tag::PERSON)),
+            )
+            .collect();
+        Self {
+            version: (item.created(), item.modified()),
+            preview,
+            markers,
+            created: item.created().format(TIMESTAMP).to_string(),
+            modified: item.modified().format(TIMESTAMP).to_string(),
+        }
+    }
+}
+
+/// The rows made so far, by entry. Every change of the content of an entry also changes its
+/// modification time, which makes its row outdated.
+#[derive(Debug, Default)]
+pub struct Rows {
+    cells: HashMap<Uuid, Cells>,
+    /// The preview settings the rows were made with.
+    settings: Option<(PreviewMode, Option<usize>)>,
+}
+
+impl Rows {
+    /// Drops every row if the previews are made differently now, or if the cache outgrew a
+    /// log of `len` entries, e.g. after many deletions.
+    pub fn prepare(&mut self, settings: (PreviewMode, Option<usize>), len: usize) {
+        if self.settings != Some(settings) || self.cells.len() > 2 * len + 64 {
+            self.cells.clear();
+            self.settings = Some(settings);
+        }
+    }
+
+    /// The row of `item`, made with `preview` unless it is cached for this version.
+    pub fn get(&mut self, item: &Item, preview: impl FnOnce(&Item) -> String) -> &Cells {
+        let version = (item.created(), item.modified());
+        match self.cells.entry(item.uuid()) {
+            Entry::Occupied(entry) if entry.get().version == version => entry.into_mut(),
+            Entry::Occupied(mut entry) => {
+                entry.insert(Cells::new(item, preview(item)));
+                entry.into_mut()
+            }
+            Entry::Vacant(entry) => entry.insert(Cells::new(item, preview(item))),
+        }
+    }
+}
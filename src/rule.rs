@@ -0,0 +1,98 @@
+use std::str::FromStr;
+
+use color_eyre::{Result, eyre::eyre};
+use ratatui::style::Color;
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+
+use crate::log::Item;
+use crate::{collate, tag};
+
+/// Highlights entries by their content, e.g. `{ "pattern": "incident", "color": "red",
+/// "icon": "⚠" }` or `{ "tag": "meeting", "icon": "📅" }`. An entry matches when it meets
+/// every condition the rule has.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Rule {
+    /// Regular expression searched in the content, ignoring case.
+    pub pattern: Option<String>,
+    /// Tag the entry mentions, with or without the leading `#`.
+    pub tag: Option<String>,
+    /// Color of the row, a name like `red` or a hex code like `#ff8800`.
+    pub color: Option<String>,
+    /// Shown in front of the preview.
+    pub icon: Option<String>,
+}
+
+/// The rules of the settings, ready to be matched against entries.
+#[derive(Debug, Default, Clone)]
+pub struct Rules(Vec<Compiled>);
+
+#[derive(Debug, Clone)]
+struct Compiled {
+    pattern: Option<Regex>,
+    tag: Option<String>,
+    color: Option<Color>,
+    icon: Option<String>,
+}
+
+impl Rules {
+    pub fn new(rules: &[Rule]) -> Result<Self> {
+        rules
+            .iter()
+            .enumerate()
+            .map(|(i, rule)| {
+                let pattern = rule
+                    .pattern
+                    .as_deref()
+                    .map(|pattern| {
+                        RegexBuilder::new(pattern)
+                            .case_insensitive(true)
+                            .build()
+                            .map_err(|e| eyre!("invalid pattern in rule {}: {e}", i + 1))
+                    })
+                    .transpose()?;
+                let color = rule
+                    .color
+                    .as_deref()
+                    .map(|color| {
+                        Color::from_str(color)
+                            .map_err(|_| eyre!("invalid color {color} in rule {}", i + 1))
+                    })
+                    .transpose()?;
+                Ok(Compiled {
+                    pattern,
+                    tag: rule
+                        .tag
+                        .as_deref()
+                        .map(|tag| collate::key(tag.trim_start_matches(tag::TAG))),
+                    color,
+                    icon: rule.icon.clone(),
+                })
+            })
+            .collect::<Result<_>>()
+            .map(Self)
+    }
+
+    /// Color of the first matching rule that sets one.
+    pub fn color(&self, item: &Item) -> Option<Color> {
+        self.matching(item).find_map(|rule| rule.color)
+    }
+
+    /// Icon of the first matching rule that sets one.
+    pub fn icon(&self, item: &Item) -> Option<&str> {
+        self.matching(item).find_map(|rule| rule.icon.as_deref())
+    }
+
+    fn matching<'a>(&'a self, item: &Item) -> impl Iterator<Item = &'a Compiled> + use<'a> {
+        let content = item.content();
+        let tags: Vec<String> = item.tags().iter().map(|t| collate::key(t)).collect();
+        self.0.iter().filter(move |rule| {
+            rule.pattern
+                .as_ref()
+                .is_none_or(|pattern| pattern.is_match(&content))
+                && rule.tag.as_ref().is_none_or(|tag| tags.contains(tag))
+                && (rule.pattern.is_some() || rule.tag.is_some())
+        })
+    }
+}
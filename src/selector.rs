@@ -0,0 +1,51 @@
+use std::fmt;
+use std::str::FromStr;
+
+use color_eyre::{Report, Result, eyre::eyre};
+
+use crate::index::SHORT_ID;
+
+/// Refers to an entry on the command line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Selector {
+    /// `last`, the most recently created entry.
+    Last,
+    /// A position counting from the most recently created entry, `1` being `last`, unless it
+    /// is also the start of an id.
+    Nth(usize),
+    /// A full id or a prefix of it, like the short ids shown in the table.
+    Id(String),
+}
+
+impl FromStr for Selector {
+    type Err = Report;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "last" => Ok(Self::Last),
+            // longer numbers, and ones with a leading zero, are taken for ids that happen to
+            // contain only digits; see `App::find` for shorter ones
+            _ if !s.is_empty()
+                && s.len() < SHORT_ID
+                && (s == "0" || !s.starts_with('0'))
+                && s.chars().all(|c| c.is_ascii_digit()) =>
+            {
+                match s.parse()? {
+                    0 => Err(eyre!("entries are counted from 1, the most recent one")),
+                    n => Ok(Self::Nth(n)),
+                }
+            }
+            _ => Ok(Self::Id(s.to_owned())),
+        }
+    }
+}
+
+impl fmt::Display for Selector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Last => f.write_str("last"),
+            Self::Nth(n) => write!(f, "{n}"),
+            Self::Id(id) => f.write_str(id),
+        }
+    }
+}
@@ -0,0 +1,173 @@
+use color_eyre::Result;
+use serde::Deserialize;
+use serde_json::json;
+use tiny_http::{Header, Method, Request, Response, Server};
+
+use crate::log::Item;
+use crate::selector::Selector;
+use crate::{Added, App, collate};
+
+#[derive(Debug, Deserialize)]
+struct Body {
+    content: String,
+}
+
+type JsonResponse = Response<std::io::Cursor<Vec<u8>>>;
+
+/// Serves the log over a small JSON API on `127.0.0.1:<port>`:
+///
+/// - `GET /entries[?q=<text>]` lists entries, optionally filtered by content
+/// - `GET /entries/<id>` returns a single entry, `<id>` may be abbreviated everywhere
+/// - `POST /entries` with `{"content": "..."}` adds an entry, unless it repeats a recent one
+///   according to the `duplicates` setting, which answers `409` or merges it
+/// - `PUT /entries/<id>` with `{"content": "..."}` updates an entry
+/// - `DELETE /entries/<id>` removes an entry
+pub fn run(port: u16) -> Result<()> {
+    let server = Server::http(("127.0.0.1", port)).map_err(|e| color_eyre::eyre::eyre!(e))?;
+    println!("listening on http://127.0.0.1:{port}");
+
+    for mut request in server.incoming_requests() {
+        let response = match handle(&mut request) {
+            Ok(response) => response,
+            Err(e) => error(500, &e.to_string()),
+        };
+        request.respond(response)?;
+    }
+    Ok(())
+}
+
+fn handle(request: &mut Request) -> Result<JsonResponse> {
+    // the log is reloaded for every request, so changes made from the TUI or CLI are picked up
+    let mut app = App::new(App::config_path().to_owned())?;
+    app.load_all()?;
+
+    let url = request.url().to_owned();
+    let (path, query) = url.split_once('?').unwrap_or((&url, ""));
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+    let id = match segments.as_slice() {
+        ["entries", id] => match app.find(&Selector::Id((*id).to_owned())) {
+            Ok(item) => item.id(),
+            Err(e) => return Ok(error(404, &e.to_string())),
+        },
+        _ => String::new(),
+    };
+
+    Ok(match (request.method(), segments.as_slice()) {
+        (Method::Get, ["entries"]) => {
+            let needle = param(query, "q");
+            let items: Vec<&Item> = app
+                .logs
+                .iter()
+                .filter(|i| {
+                    needle
+                        .as_ref()
+                        .is_none_or(|n| collate::contains(&i.content(), n))
+                })
+                .collect();
+            ok(200, &items)?
+        }
+        (Method::Get, ["entries", _]) => match app.logs.iter().find(|i| i.id() == id) {
+            Some(item) => ok(200, item)?,
+            None => error(404, "entry not found"),
+        },
+        (Method::Post, ["entries"]) => {
+            let Some(body) = body(request) else {
+                return Ok(error(400, "expected a JSON body with content"));
+            };
+            if body.content.trim().is_empty() {
+                return Ok(error(400, "content must not be empty"));
+            }
+            let item = Item::from(body.content);
+            // repeats of a recent entry are handled like `lw add` does without a terminal
+            match app.add_unique(item.clone()) {
+                Added::New => {
+                    app.save()?;
+                    ok(201, &item)?
+                }
+                Added::Merged(duplicate) => {
+                    app.save()?;
+                    match app.logs.iter().find(|i| i.id() == duplicate.id()) {
+                        Some(merged) => ok(200, merged)?,
+                        None => error(404, "entry not found"),
+                    }
+                }
+                Added::Skipped(duplicate) | Added::Confirm { duplicate, .. } => error(
+                    409,
+                    &format!("skipped duplicate of {}", app.short_id(&duplicate)),
+                ),
+            }
+        }
+        (Method::Put, ["entries", _]) => {
+            let Some(body) = body(request) else {
+                return Ok(error(400, "expected a JSON body with content"));
+            };
+            if body.content.trim().is_empty() {
+                return Ok(error(400, "content must not be empty"));
+            }
+            app.update(&id, &body.content);
+            app.save()?;
+            match app.logs.iter().find(|i| i.id() == id) {
+                Some(item) => ok(200, item)?,
+                None => error(404, "entry not found"),
+            }
+        }
+        (Method::Delete, ["entries", _]) => {
+            app.remove(&id);
+            app.save()?;
+            Response::from_data(Vec::new()).with_status_code(204)
+        }
+        (_, ["entries"]) | (_, ["entries", _]) => error(405, "method not allowed"),
+        _ => error(404, "not found"),
+    })
+}
+
+fn body(request: &mut Request) -> Option<Body> {
+    let mut content = String::new();
+    request.as_reader().read_to_string(&mut content).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+fn ok<T: serde::Serialize + ?Sized>(status: u16, value: &T) -> Result<JsonResponse> {
+    Ok(json_response(status, serde_json::to_vec(value)?))
+}
+
+fn error(status: u16, message: &str) -> JsonResponse {
+    json_response(status, json!({ "error": message }).to_string().into_bytes())
+}
+
+fn json_response(status: u16, data: Vec<u8>) -> JsonResponse {
+    let mut response = Response::from_data(data).with_status_code(status);
+    if let Ok(header) = Header::from_bytes("Content-Type", "application/json") {
+        response.add_header(header);
+    }
+    response
+}
+
+/// Looks up `key` in a query string, decoding `+` and percent escapes.
+fn param(query: &str, key: &str) -> Option<String> {
+    query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(k, _)| *k == key)
+        .map(|(_, v)| decode(v))
+}
+
+fn decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => out.push(b' '),
+            b'%' if let Some(hex) = s.get(i + 1..i + 3)
+                && let Ok(b) = u8::from_str_radix(hex, 16) =>
+            {
+                out.push(b);
+                i += 2;
+            }
+            b => out.push(b),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
@@ -0,0 +1,316 @@
+use std::collections::BTreeMap;
+use std::{fs, path::PathBuf};
+
+use chrono::NaiveTime;
+use color_eyre::{Result, eyre::eyre};
+use serde::{Deserialize, Serialize};
+
+use crate::goal::Goal;
+use crate::hook::Hooks;
+use crate::i18n::{self, Language};
+use crate::rule::Rule;
+use crate::storage::Corrupt;
+use crate::{App, week};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LengthPolicy {
+    #[default]
+    Warn,
+    Split,
+}
+
+/// How entries are condensed into a single table row.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PreviewMode {
+    /// First non-empty line of the entry.
+    #[default]
+    FirstLine,
+    /// The whole entry with line breaks replaced by spaces.
+    Flatten,
+}
+
+/// Row height of the main table.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Density {
+    /// One line per entry, without tag and project chips.
+    Compact,
+    #[default]
+    Comfortable,
+    /// Three lines per entry and additional id and modified columns.
+    Spacious,
+}
+
+impl Density {
+    pub fn next(self) -> Self {
+        match self {
+            Self::Compact => Self::Comfortable,
+            Self::Comfortable => Self::Spacious,
+            Self::Spacious => Self::Compact,
+        }
+    }
+
+    pub fn height(self) -> u16 {
+        match self {
+            Self::Compact => 1,
+            Self::Comfortable => 2,
+            Self::Spacious => 3,
+        }
+    }
+}
+
+/// Order of the main table. Pinned entries always come first.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    /// Most recently created first.
+    #[default]
+    Created,
+    /// By content, ignoring case and accents.
+    Alphabetical,
+}
+
+impl SortOrder {
+    pub fn toggle(self) -> Self {
+        match self {
+            Self::Created => Self::Alphabetical,
+            Self::Alphabetical => Self::Created,
+        }
+    }
+}
+
+/// Encoding of the data file and the monthly shards.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Compression {
+    /// Pretty-printed JSON.
+    #[default]
+    None,
+    /// Gzip compressed JSON.
+    Gzip,
+}
+
+/// How many snapshots of the log are kept in the backups directory. A snapshot is kept if
+/// any of the tiers selects it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Backups {
+    /// Most recent snapshots.
+    pub keep_last: usize,
+    /// Newest snapshot of each of the most recent days.
+    pub daily: usize,
+    /// Newest snapshot of each of the most recent weeks.
+    pub weekly: usize,
+    /// Newest snapshot of each of the most recent months.
+    pub monthly: usize,
+}
+
+impl Default for Backups {
+    fn default() -> Self {
+        Self {
+            keep_last: 5,
+            daily: 7,
+            weekly: 4,
+            monthly: 12,
+        }
+    }
+}
+
+/// What happens when an added entry repeats one created shortly before.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DuplicateAction {
+    /// Add it anyway.
+    Allow,
+    /// Drop the new entry.
+    Skip,
+    /// Replace the content of the earlier entry with the new one.
+    Merge,
+    /// Ask whether to add it, skipping it when there is no one to ask.
+    #[default]
+    Prompt,
+}
+
+/// Detection of entries added twice, e.g. by a script running the quick add twice.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Duplicates {
+    /// How far back to look for an entry with the same content.
+    pub within_minutes: u32,
+    pub action: DuplicateAction,
+}
+
+impl Default for Duplicates {
+    fn default() -> Self {
+        Self {
+            within_minutes: 5,
+            action: DuplicateAction::default(),
+        }
+    }
+}
+
+/// Arguments of `lw export` saved under a name, used with `--preset <name>`. Arguments given
+/// on the command line take precedence.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExportPreset {
+    /// Search the exported entries have to match, e.g. `project:acme is:billable`.
+    pub query: Option<String>,
+    /// `day`, `week`, `month`, `all` or the day to start from, e.g. `last monday`.
+    pub period: Option<String>,
+    pub output: Option<PathBuf>,
+}
+
+/// Type of a custom field declared in `fields`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FieldType {
+    #[default]
+    String,
+    Bool,
+    Number,
+}
+
+impl FieldType {
+    /// Checks `value` against the type, returning it in the form it is stored in.
+    pub fn parse(self, value: &str) -> Result<String> {
+        let value = value.trim();
+        match self {
+            Self::String => Ok(value.to_owned()),
+            Self::Bool => match value.to_lowercase().as_str() {
+                "true" | "yes" | "y" | "1" => Ok("true".to_owned()),
+                "false" | "no" | "n" | "0" => Ok("false".to_owned()),
+                _ => Err(eyre!("expected yes or no, got {value}")),
+            },
+            Self::Number => value
+                .parse::<f64>()
+                .map(|_| value.to_owned())
+                .map_err(|_| eyre!("expected a number, got {value}")),
+        }
+    }
+}
+
+/// First day of the week in the week view, weekly reports and dates like `last week`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WeekStart {
+    #[default]
+    Monday,
+    Sunday,
+}
+
+impl WeekStart {
+    /// Sunday in the countries that count their weeks from it, e.g. for `en_US`, and Monday
+    /// everywhere else.
+    pub fn detect() -> Self {
+        match i18n::region().as_deref() {
+            Some("US" | "CA" | "MX" | "BR" | "JP" | "KR" | "IL" | "PH" | "IN") => Self::Sunday,
+            _ => Self::Monday,
+        }
+    }
+
+    pub fn weekday(self) -> chrono::Weekday {
+        match self {
+            Self::Monday => chrono::Weekday::Mon,
+            Self::Sunday => chrono::Weekday::Sun,
+        }
+    }
+}
+
+/// Remote location the log is synchronized with by `lw sync`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "backend", rename_all = "lowercase")]
+pub enum Remote {
+    /// A file on a WebDAV server, e.g. Nextcloud.
+    Webdav {
+        url: String,
+        username: Option<String>,
+        password: Option<String>,
+    },
+    /// An object in an S3-compatible bucket, addressed path-style.
+    S3 {
+        endpoint: String,
+        region: String,
+        bucket: String,
+        key: String,
+        access_key: String,
+        secret_key: String,
+    },
+}
+
+/// User preferences, read from `settings.json` next to the log file.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// Maximum number of characters per entry before `on_exceed` applies.
+    pub max_length: Option<usize>,
+    pub on_exceed: LengthPolicy,
+    pub preview_mode: PreviewMode,
+    /// Maximum number of characters shown per row, cut at a word boundary.
+    pub preview_length: Option<usize>,
+    /// Colors for tags and projects, e.g. `"#meeting": "yellow"` or `"+acme": "#ff8800"`.
+    pub colors: BTreeMap<String, String>,
+    /// Initial row density, can be changed at runtime with `<z>`.
+    pub density: Density,
+    /// Initial order of the table, can be toggled at runtime with `<S>`.
+    pub sort: SortOrder,
+    pub sync: Option<Remote>,
+    /// Incoming webhook URL used by `lw post slack`, which determines the channel.
+    pub slack_webhook: Option<String>,
+    /// Snapshot the log into the backups directory when the TUI exits.
+    pub backups: Option<Backups>,
+    /// Encoding used when writing the log, applied to existing files by `lw compact`.
+    pub compression: Compression,
+    pub duplicates: Duplicates,
+    /// Custom fields shown in the editor, e.g. `"ticket": "string"` or `"billable": "bool"`.
+    pub fields: BTreeMap<String, FieldType>,
+    /// Daily logging target, shown in the title bar and the week view.
+    pub goal: Option<Goal>,
+    /// Colors and icons of the entries matching a pattern or tag, the first match wins.
+    pub rules: Vec<Rule>,
+    /// Language of the TUI, taken from the locale unless configured.
+    pub language: Option<Language>,
+    /// Taken from the locale unless configured.
+    pub week_start: Option<WeekStart>,
+    /// Commands run for the added, updated and deleted entries after each save.
+    pub hooks: Hooks,
+    /// Spell checker speaking the ispell protocol, e.g. `aspell -a --lang=de`, used when
+    /// built with the `spellcheck` feature. `hunspell -a` unless configured.
+    pub spellcheck: Option<String>,
+    /// Whether `<ESC>` quits from the main view like `<q>`, unless set to `false`. With
+    /// unsaved changes it has to be pressed twice.
+    pub quit_on_esc: Option<bool>,
+    /// Ask before quitting even when everything is saved.
+    pub confirm_quit: bool,
+    /// Named sets of `lw export` arguments, e.g. a monthly report for a client.
+    pub export_presets: BTreeMap<String, ExportPreset>,
+    /// Time of day, e.g. `17:00`, from which the first start of the TUI opens the review of
+    /// today's entries.
+    pub review_after: Option<NaiveTime>,
+    /// Whether saves append the changed entries to `audit.jsonl`, unless set to `false`.
+    pub audit: Option<bool>,
+}
+
+impl Settings {
+    pub fn path() -> PathBuf {
+        App::config_path().with_file_name("settings.json")
+    }
+
+    pub fn load() -> Result<Self> {
+        let path = Self::path();
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(&path)?;
+        Ok(serde_json::from_str(&content).map_err(|e| Corrupt::parse(&path, e))?)
+    }
+
+    /// Switches to the configured language and first day of the week, which dates are read
+    /// and shown in.
+    pub fn apply_locale(&self) {
+        self.language.unwrap_or_else(Language::detect).set();
+        week::set_first_day(self.week_start.unwrap_or_else(WeekStart::detect).weekday());
+    }
+}
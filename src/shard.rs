@@ -0,0 +1,136 @@
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::NaiveDate;
+use color_eyre::{Result, eyre::eyre};
+
+use crate::log::Item;
+use crate::settings::Compression;
+use crate::storage;
+
+static FORMAT: &str = "%Y-%m";
+
+/// The shard an entry is stored in, e.g. `2024-06`.
+pub fn month(item: &Item) -> String {
+    month_of(item.created().date_naive())
+}
+
+/// The shard entries created on `day` are stored in.
+pub fn month_of(day: NaiveDate) -> String {
+    day.format(FORMAT).to_string()
+}
+
+/// Entries stored as one file per month next to the data file, so startup only has to parse
+/// the current month. Older months are loaded on demand.
+#[derive(Debug, Clone, Default)]
+pub struct Shards {
+    dir: PathBuf,
+    /// Months whose entries are in memory. Only these are written on save.
+    loaded: BTreeSet<String>,
+    /// Months on disk that were not loaded yet, oldest first.
+    pending: Vec<String>,
+}
+
+impl Shards {
+    /// Lists the shards stored next to `config` without loading any of them.
+    pub fn open(config: &Path) -> Result<Self> {
+        let dir = config.with_file_name("logs");
+        let mut pending = Vec::new();
+        if dir.exists() {
+            for entry in fs::read_dir(&dir)? {
+                let path = entry?.path();
+                if path.extension().is_some_and(|e| e == "json")
+                    && let Some(stem) = path.file_stem().and_then(|s| s.to_str())
+                {
+                    pending.push(stem.to_owned());
+                }
+            }
+        }
+        pending.sort();
+        Ok(Self {
+            dir,
+            loaded: BTreeSet::new(),
+            pending,
+        })
+    }
+
+    /// Takes over entries read from a data file that still contains the whole log. Every
+    /// month, including the ones only found on disk, is rewritten from them on the next save.
+    pub fn adopt(&mut self, logs: &[Item]) {
+        self.loaded.extend(self.pending.drain(..));
+        self.loaded.extend(logs.iter().map(month));
+    }
+
+    /// Whether every month is in memory.
+    pub fn is_complete(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Loads the newest month that is not in memory yet.
+    pub fn load_older(&mut self) -> Result<Vec<Item>> {
+        match self.pending.pop() {
+            Some(month) => self.read(month),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Loads `month` unless it already is in memory.
+    pub fn load(&mut self, month: &str) -> Result<Vec<Item>> {
+        match self.pending.iter().position(|m| m == month) {
+            Some(position) => {
+                let month = self.pending.remove(position);
+                self.read(month)
+            }
+            None => {
+                self.loaded.insert(month.to_owned());
+                Ok(Vec::new())
+            }
+        }
+    }
+
+    pub fn load_all(&mut self) -> Result<Vec<Item>> {
+        let mut items = Vec::new();
+        while !self.is_complete() {
+            items.extend(self.load_older()?);
+        }
+        Ok(items)
+    }
+
+    fn read(&mut self, month: String) -> Result<Vec<Item>> {
+        let path = self.path(&month);
+        let content = storage::read(&path)?;
+        let items =
+            serde_json::from_str(&content).map_err(|e| storage::Corrupt::parse(&path, e))?;
+        self.loaded.insert(month);
+        Ok(items)
+    }
+
+    /// Writes the shard of every loaded month, removing the ones left without entries.
+    pub fn save(&mut self, logs: &[Item], compression: Compression) -> Result<()> {
+        if let Some(month) = logs.iter().map(month).find(|m| self.pending.contains(m)) {
+            return Err(eyre!(
+                "entries of {month} are not loaded, refusing to overwrite them"
+            ));
+        }
+        self.loaded.extend(logs.iter().map(month));
+        fs::create_dir_all(&self.dir)
+            .map_err(|e| eyre!("failed to create {}: {e}", self.dir.display()))?;
+        for month in &self.loaded {
+            let items: Vec<&Item> = logs.iter().filter(|i| self::month(i) == *month).collect();
+            let path = self.path(month);
+            if items.is_empty() {
+                if path.exists() {
+                    fs::remove_file(path)?;
+                }
+            } else {
+                storage::write(&path, &items, compression)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn path(&self, month: &str) -> PathBuf {
+        self.dir.join(format!("{month}.json"))
+    }
+}
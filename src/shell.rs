@@ -0,0 +1,128 @@
+use std::str::FromStr;
+
+use color_eyre::{Report, Result, eyre::eyre};
+
+/// A shell `lw shell-hook` prints the integration for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shell {
+    Zsh,
+    Bash,
+    Fish,
+}
+
+impl FromStr for Shell {
+    type Err = Report;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "zsh" => Ok(Self::Zsh),
+            "bash" => Ok(Self::Bash),
+            "fish" => Ok(Self::Fish),
+            other => Err(eyre!("unknown shell {other}, expected zsh, bash or fish")),
+        }
+    }
+}
+
+impl Shell {
+    /// Defines `lwl`, which logs the last command with its directory and exit status as an
+    /// entry tagged `#shell`, and binds it to `<CTRL-x> <CTRL-l>`. Words given to `lwl` are
+    /// added as a note.
+    pub fn hook(self) -> &'static str {
+        match self {
+            Self::Zsh => ZSH,
+            Self::Bash => BASH,
+            Self::Fish => FISH,
+        }
+    }
+}
+
+static ZSH: &str = r#"# lw shell integration, add to ~/.zshrc: eval "$(lw shell-hook zsh)"
+_lw_preexec() {
+  if [[ $1 == lwl || $1 == "lwl "* ]]; then
+    _lw_skip=1
+    return
+  fi
+  _lw_command=$1
+  _lw_dir=$PWD
+}
+_lw_precmd() {
+  local code=$?
+  if (( _lw_skip )); then
+    _lw_skip=0
+    return
+  fi
+  _lw_status=$code
+}
+lwl() {
+  if [[ -z $_lw_command ]]; then
+    print -u2 "lw: no command to log yet"
+    return 1
+  fi
+  local text="\$ $_lw_command"$'\n'"exit $_lw_status in $_lw_dir"
+  (( $# )) && text+=$'\n'"$*"
+  command lw add --tag shell "$text"
+}
+_lw_widget() {
+  zle -I
+  lwl
+}
+preexec_functions+=(_lw_preexec)
+precmd_functions=(_lw_precmd $precmd_functions)
+zle -N _lw_widget
+bindkey '^X^L' _lw_widget
+"#;
+
+static BASH: &str = r#"# lw shell integration, add to ~/.bashrc: eval "$(lw shell-hook bash)"
+_lw_prompt() {
+  local code=$? entry
+  entry=$(HISTTIMEFORMAT= builtin history 1)
+  if [[ $entry != "$_lw_entry" ]]; then
+    _lw_entry=$entry
+    [[ $entry =~ ^\ *[0-9]+\*?\ +(.*)$ ]] && entry=${BASH_REMATCH[1]}
+    case $entry in
+      lwl | "lwl "*) ;;
+      *)
+        _lw_command=$entry
+        _lw_status=$code
+        _lw_dir=$PWD
+        ;;
+    esac
+  fi
+  return $code
+}
+lwl() {
+  if [[ -z $_lw_command ]]; then
+    echo "lw: no command to log yet" >&2
+    return 1
+  fi
+  local text="\$ $_lw_command"$'\n'"exit $_lw_status in $_lw_dir"
+  (( $# )) && text+=$'\n'"$*"
+  command lw add --tag shell "$text"
+}
+PROMPT_COMMAND="_lw_prompt${PROMPT_COMMAND:+;$PROMPT_COMMAND}"
+[[ $- == *i* ]] && bind -x '"\C-x\C-l": lwl'
+"#;
+
+static FISH: &str = r#"# lw shell integration, add to ~/.config/fish/config.fish: lw shell-hook fish | source
+function _lw_postexec --on-event fish_postexec
+    set -l code $status
+    if test -z "$argv[1]"; or string match -qr '^lwl( |$)' -- $argv[1]
+        return
+    end
+    set -g _lw_command $argv[1]
+    set -g _lw_status $code
+    set -g _lw_dir $PWD
+end
+function lwl --description 'Log the last command with lw'
+    if not set -q _lw_command
+        echo 'lw: no command to log yet' >&2
+        return 1
+    end
+    set -l text "\$ $_lw_command" "exit $_lw_status in $_lw_dir"
+    if set -q argv[1]
+        set -a text "$argv"
+    end
+    command lw add --tag shell (string join \n -- $text)
+end
+bind \cx\cl 'lwl; commandline -f repaint'
+"#;
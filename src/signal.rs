@@ -0,0 +1,90 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::Duration;
+
+use color_eyre::Result;
+
+#[cfg(unix)]
+static GRACE_PERIOD: Duration = Duration::from_secs(1);
+
+/// Set while the log is written, so the forced exit after a signal does not cut a save short.
+static SAVING: AtomicBool = AtomicBool::new(false);
+
+/// Tracks termination requests delivered as signals while the TUI is running.
+#[derive(Debug, Default, Clone)]
+pub struct Signals {
+    terminate: Arc<AtomicBool>,
+    /// Set once the event loop saw the request and is shutting down on its own.
+    acknowledged: Arc<AtomicBool>,
+}
+
+impl Signals {
+    #[cfg(unix)]
+    pub fn register() -> Result<Self> {
+        use signal_hook::consts::{SIGHUP, SIGTERM};
+
+        let signals = Self::default();
+        let mut incoming = signal_hook::iterator::Signals::new([SIGTERM, SIGHUP])?;
+        let terminate = Arc::clone(&signals.terminate);
+        let acknowledged = Arc::clone(&signals.acknowledged);
+        thread::spawn(move || {
+            if let Some(signal) = incoming.forever().next() {
+                terminate.store(true, Ordering::SeqCst);
+                // Reading from a terminal that was closed can block the event loop for good,
+                // so exit anyway if it did not get to the request in time, but never during a
+                // save.
+                thread::sleep(GRACE_PERIOD);
+                while SAVING.load(Ordering::SeqCst) {
+                    thread::sleep(GRACE_PERIOD / 10);
+                }
+                if !acknowledged.load(Ordering::SeqCst) {
+                    std::process::exit(128 + signal);
+                }
+            }
+        });
+        Ok(signals)
+    }
+
+    #[cfg(not(unix))]
+    pub fn register() -> Result<Self> {
+        Ok(Self::default())
+    }
+
+    /// Whether a termination was requested, which the caller then takes care of.
+    pub fn terminated(&self) -> bool {
+        let terminated = self.terminate.load(Ordering::SeqCst);
+        if terminated {
+            self.acknowledged.store(true, Ordering::SeqCst);
+        }
+        terminated
+    }
+}
+
+/// Marks a save as running until it is dropped.
+pub struct Saving(());
+
+impl Saving {
+    pub fn start() -> Self {
+        SAVING.store(true, Ordering::SeqCst);
+        Self(())
+    }
+}
+
+impl Drop for Saving {
+    fn drop(&mut self) {
+        SAVING.store(false, Ordering::SeqCst);
+    }
+}
+
+/// Stops the process the way a shell expects on `Ctrl-z`. Returns once it is resumed with `fg`.
+#[cfg(unix)]
+pub fn suspend() -> Result<()> {
+    signal_hook::low_level::raise(signal_hook::consts::SIGTSTP)?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+pub fn suspend() -> Result<()> {
+    Ok(())
+}
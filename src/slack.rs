@@ -0,0 +1,50 @@
+use chrono::NaiveDate;
+use color_eyre::{Result, eyre::eyre};
+use ureq::Agent;
+
+use crate::log::Item;
+
+/// Formats the entries created on `day` as a Slack message.
+pub fn message(logs: &[Item], day: NaiveDate) -> String {
+    let mut items: Vec<&Item> = logs
+        .iter()
+        .filter(|item| item.created().date_naive() == day)
+        .collect();
+    items.sort_by_key(|item| item.created());
+
+    let mut text = format!("*Update for {}*", day.format("%A, %Y-%m-%d"));
+    if items.is_empty() {
+        text.push_str("\n_No entries._");
+    }
+    for item in items {
+        text.push_str("\n• ");
+        text.push_str(&escape(&item.content()).replace('\n', "\n    "));
+    }
+    text
+}
+
+/// Posts `text` to a Slack incoming webhook.
+pub fn post(webhook: &str, text: &str) -> Result<()> {
+    let agent: Agent = Agent::config_builder()
+        .http_status_as_error(false)
+        .build()
+        .into();
+    let mut response = agent
+        .post(webhook)
+        .header("Content-Type", "application/json")
+        .send(serde_json::to_vec(&serde_json::json!({ "text": text }))?.as_slice())?;
+    match response.status().as_u16() {
+        200..=299 => Ok(()),
+        status => Err(eyre!(
+            "failed to post to Slack: HTTP {status} {}",
+            response.body_mut().read_to_string().unwrap_or_default()
+        )),
+    }
+}
+
+/// Escapes the characters Slack treats as control sequences.
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
@@ -0,0 +1,247 @@
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Write};
+use std::ops::Range;
+use std::process::{self, Child, ChildStdin, ChildStdout, Stdio};
+
+use color_eyre::{Result, eyre::eyre};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+
+use crate::tag;
+
+/// Used unless `spellcheck` is set in the settings.
+pub static DEFAULT_COMMAND: &str = "hunspell -a";
+
+/// A spell checker speaking the ispell pipe protocol, like `hunspell -a` or `aspell -a`.
+/// Words are checked once and remembered.
+#[derive(Debug)]
+struct Checker {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+    /// Suggestions for each misspelled word, `None` for a correct one.
+    known: HashMap<String, Option<Vec<String>>>,
+}
+
+impl Checker {
+    fn start(command: &str) -> Result<Self> {
+        let mut words = command.split_whitespace();
+        let program = words
+            .next()
+            .ok_or_else(|| eyre!("no spell checker configured"))?;
+        let mut child = process::Command::new(program)
+            .args(words)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| eyre!("failed to run {command}: {e}"))?;
+        let (Some(stdin), Some(stdout)) = (child.stdin.take(), child.stdout.take()) else {
+            return Err(eyre!("failed to run {command}"));
+        };
+        let mut checker = Self {
+            child,
+            stdin,
+            stdout: BufReader::new(stdout),
+            known: HashMap::new(),
+        };
+        let banner = checker.read_line()?;
+        if !banner.starts_with("@(#)") {
+            return Err(eyre!("{command} does not speak the ispell protocol"));
+        }
+        Ok(checker)
+    }
+
+    fn read_line(&mut self) -> Result<String> {
+        let mut line = String::new();
+        if self.stdout.read_line(&mut line)? == 0 {
+            return Err(eyre!("the spell checker exited"));
+        }
+        Ok(line.trim_end().to_owned())
+    }
+
+    /// Suggestions for `word` if it is misspelled.
+    fn check(&mut self, word: &str) -> Result<Option<&[String]>> {
+        if !self.known.contains_key(word) {
+            // `^` keeps a word starting with a protocol command from being taken as one
+            writeln!(self.stdin, "^{word}")?;
+            self.stdin.flush()?;
+            let mut suggestions = None;
+            loop {
+                let line = self.read_line()?;
+                if line.is_empty() {
+                    break;
+                }
+                if line.starts_with('#') {
+                    suggestions.get_or_insert_with(Vec::new);
+                } else if line.starts_with('&') {
+                    let list = line.split_once(": ").map_or("", |(_, list)| list);
+                    suggestions
+                        .get_or_insert_with(Vec::new)
+                        .extend(list.split(", ").map(str::to_owned));
+                }
+            }
+            self.known.insert(word.to_owned(), suggestions);
+        }
+        Ok(self.known[word].as_deref())
+    }
+}
+
+impl Drop for Checker {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// The words of `text` worth checking: tags, projects, people and links are left out, and
+/// so are single letters.
+fn words(text: &str) -> Vec<Range<usize>> {
+    let mut words = Vec::new();
+    let mut offset = 0;
+    for token in text.split_inclusive(char::is_whitespace) {
+        let start = offset;
+        offset += token.len();
+        if token.starts_with([tag::TAG, tag::PROJECT, tag::PERSON]) || token.contains("://") {
+            continue;
+        }
+        let mut word: Option<usize> = None;
+        for (i, c) in token.char_indices().chain([(token.len(), ' ')]) {
+            let inside = c.is_alphabetic()
+                || (c == '\'' && word.is_some() && token[i + 1..].starts_with(char::is_alphabetic));
+            match (inside, word) {
+                (true, None) => word = Some(i),
+                (false, Some(begin)) => {
+                    if token[begin..i].chars().nth(1).is_some() {
+                        words.push(start + begin..start + i);
+                    }
+                    word = None;
+                }
+                _ => {}
+            }
+        }
+    }
+    words
+}
+
+/// A misspelled word being corrected in the editor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Suggestions {
+    pub word: Range<usize>,
+    pub candidates: Vec<String>,
+    pub selected: usize,
+}
+
+/// Spell checking of the entry in the editor. The checker is started when first needed, and
+/// not again once it failed.
+#[derive(Debug, Default)]
+pub struct Spelling {
+    checker: Option<Checker>,
+    failed: bool,
+    pub popup: Option<Suggestions>,
+}
+
+impl Spelling {
+    /// Checks nothing.
+    pub fn off() -> Self {
+        Self {
+            failed: true,
+            ..Self::default()
+        }
+    }
+
+    fn checker(&mut self, command: &str) -> Result<Option<&mut Checker>> {
+        if self.failed {
+            return Ok(None);
+        }
+        if self.checker.is_none() {
+            match Checker::start(command) {
+                Ok(checker) => self.checker = Some(checker),
+                Err(e) => {
+                    self.failed = true;
+                    return Err(e);
+                }
+            }
+        }
+        Ok(self.checker.as_mut())
+    }
+
+    /// Runs `f` with the checker, turning it off when it fails.
+    fn with<T: Default>(
+        &mut self,
+        command: &str,
+        f: impl FnOnce(&mut Checker) -> Result<T>,
+    ) -> Result<T> {
+        let Some(checker) = self.checker(command)? else {
+            return Ok(T::default());
+        };
+        f(checker).inspect_err(|_| {
+            self.checker = None;
+            self.failed = true;
+        })
+    }
+
+    /// The misspelled words of `text`.
+    pub fn misspelled(&mut self, command: &str, text: &str) -> Result<Vec<Range<usize>>> {
+        self.with(command, |checker| {
+            let mut misspelled = Vec::new();
+            for word in words(text) {
+                if checker.check(&text[word.clone()])?.is_some() {
+                    misspelled.push(word);
+                }
+            }
+            Ok(misspelled)
+        })
+    }
+
+    /// The misspelled word at `cursor` or right before it, with the suggestions for it.
+    pub fn suggest(
+        &mut self,
+        command: &str,
+        text: &str,
+        cursor: usize,
+    ) -> Result<Option<Suggestions>> {
+        let Some(word) = words(text)
+            .into_iter()
+            .take_while(|word| word.start <= cursor)
+            .last()
+            .filter(|word| word.end >= cursor)
+        else {
+            return Ok(None);
+        };
+        self.with(command, |checker| {
+            Ok(checker
+                .check(&text[word.clone()])?
+                .map(|candidates| Suggestions {
+                    word,
+                    candidates: candidates.to_vec(),
+                    selected: 0,
+                }))
+        })
+    }
+}
+
+/// The `row` of `text` with its misspelled words underlined.
+pub fn underline<'a>(text: &'a str, row: &Range<usize>, misspelled: &[Range<usize>]) -> Line<'a> {
+    let style = Style::default()
+        .add_modifier(Modifier::UNDERLINED)
+        .underline_color(Color::LightRed);
+    let mut spans = Vec::new();
+    let mut at = row.start;
+    for word in misspelled {
+        let start = word.start.max(row.start);
+        let end = word.end.min(row.end);
+        if start >= end {
+            continue;
+        }
+        if at < start {
+            spans.push(Span::raw(&text[at..start]));
+        }
+        spans.push(Span::styled(&text[start..end], style));
+        at = end;
+    }
+    if at < row.end {
+        spans.push(Span::raw(&text[at..row.end]));
+    }
+    Line::from(spans)
+}
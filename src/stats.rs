@@ -0,0 +1,327 @@
+use std::collections::BTreeMap;
+
+use chrono::{NaiveDate, TimeDelta, Timelike};
+use ratatui::prelude::*;
+use ratatui::widgets::{Bar, BarChart, BarGroup, Block, Paragraph, Sparkline, Widget};
+use ratatui::{style::Color, symbols::border, text::Line};
+use unicode_width::UnicodeWidthStr;
+
+use crate::i18n::{self, t};
+use crate::log::Item;
+use crate::{COLOR_PRIMARY, COLOR_SECONDARY, COLOR_TERTIARY, focus, tag};
+
+/// Number of days, up to today, covered by the project activity.
+pub const ACTIVITY_DAYS: usize = 30;
+/// Most projects listed below the time of day chart.
+static MAX_PROJECTS: usize = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HourDistribution([u64; 24]);
+
+impl HourDistribution {
+    pub fn new<'a, I: IntoIterator<Item = &'a Item>>(items: I) -> Self {
+        let mut hours = [0; 24];
+        for item in items {
+            hours[item.created().hour() as usize] += 1;
+        }
+        Self(hours)
+    }
+
+    pub fn hours(&self) -> &[u64; 24] {
+        &self.0
+    }
+
+    pub fn total(&self) -> u64 {
+        self.0.iter().sum()
+    }
+
+    pub fn busiest(&self) -> Option<usize> {
+        if self.total() == 0 {
+            return None;
+        }
+        self.0
+            .iter()
+            .enumerate()
+            .max_by_key(|(hour, count)| (**count, std::cmp::Reverse(*hour)))
+            .map(|(hour, _)| hour)
+    }
+
+    pub fn to_text(&self, width: usize) -> String {
+        let max = self.0.iter().copied().max().unwrap_or(0).max(1);
+        self.0
+            .iter()
+            .enumerate()
+            .map(|(hour, count)| {
+                let len = (*count as usize * width).div_ceil(max as usize);
+                format!("{hour:02} | {:<width$} {count}", "█".repeat(len))
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+/// Order of the projects in the statistics, toggled with `<S>`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ProjectSort {
+    /// Most entries first.
+    #[default]
+    Entries,
+    /// Most time tracked first.
+    Time,
+}
+
+impl ProjectSort {
+    pub fn toggle(self) -> Self {
+        match self {
+            Self::Entries => Self::Time,
+            Self::Time => Self::Entries,
+        }
+    }
+}
+
+/// Entries and tracked time of a project per day of the last [`ACTIVITY_DAYS`] days, oldest
+/// first. An entry counts for each project it mentions, on the day it was created.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProjectActivity {
+    pub name: String,
+    pub entries: [u64; ACTIVITY_DAYS],
+    /// Seconds.
+    pub tracked: [u64; ACTIVITY_DAYS],
+}
+
+impl ProjectActivity {
+    /// The projects active in the days up to `today`, sorted by `sort`.
+    pub fn new<'a, I: IntoIterator<Item = &'a Item>>(
+        items: I,
+        today: NaiveDate,
+        sort: ProjectSort,
+    ) -> Vec<Self> {
+        let mut projects: BTreeMap<String, Self> = BTreeMap::new();
+        for item in items {
+            let age = (today - item.created().date_naive()).num_days();
+            let Some(day) = usize::try_from(age)
+                .ok()
+                .and_then(|age| ACTIVITY_DAYS.checked_sub(age + 1))
+            else {
+                continue;
+            };
+            for name in item.projects() {
+                let project = projects.entry(name.clone()).or_insert_with(|| Self {
+                    name,
+                    entries: [0; ACTIVITY_DAYS],
+                    tracked: [0; ACTIVITY_DAYS],
+                });
+                project.entries[day] += 1;
+                project.tracked[day] += u64::try_from(item.tracked().num_seconds()).unwrap_or(0);
+            }
+        }
+        let mut projects: Vec<Self> = projects.into_values().collect();
+        projects.sort_by_key(|project| {
+            std::cmp::Reverse(match sort {
+                ProjectSort::Time => (project.total_tracked(), project.total_entries()),
+                ProjectSort::Entries => (project.total_entries(), project.total_tracked()),
+            })
+        });
+        projects
+    }
+
+    pub fn total_entries(&self) -> u64 {
+        self.entries.iter().sum()
+    }
+
+    pub fn total_tracked(&self) -> u64 {
+        self.tracked.iter().sum()
+    }
+
+    /// What the sparkline shows for `sort`.
+    pub fn days(&self, sort: ProjectSort) -> &[u64; ACTIVITY_DAYS] {
+        match sort {
+            ProjectSort::Time => &self.tracked,
+            ProjectSort::Entries => &self.entries,
+        }
+    }
+}
+
+pub struct StatsView<'a> {
+    pub logs: &'a [Item],
+    pub today: NaiveDate,
+    pub sort: ProjectSort,
+    /// Colors of the projects, from the settings.
+    pub colors: &'a BTreeMap<String, String>,
+}
+
+impl Widget for StatsView<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let distribution = HourDistribution::new(self.logs);
+
+        let block = Block::bordered()
+            .title(
+                Line::from(Span::styled(
+                    format!(" {} ", t("Statistics")),
+                    Style::default().fg(COLOR_PRIMARY).bold(),
+                ))
+                .centered(),
+            )
+            .title_bottom(
+                Line::from(vec![
+                    Span::raw(format!(" {} ", t("Back"))),
+                    Span::styled(
+                        "<s> | <q> | <ESC>",
+                        Style::default()
+                            .fg(COLOR_PRIMARY)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Span::raw(format!(" {} ", t("Sort"))),
+                    Span::styled(
+                        "<S>",
+                        Style::default()
+                            .fg(COLOR_PRIMARY)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                ])
+                .centered(),
+            )
+            .title_style(Color::White)
+            .border_set(border::THICK)
+            .border_style(Color::White);
+
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let projects = ProjectActivity::new(self.logs, self.today, self.sort);
+        let listed = projects.len().min(MAX_PROJECTS);
+        let projects_height = if listed == 0 { 0 } else { listed as u16 + 2 };
+        let [summary_area, chart_area, projects_area] = Layout::vertical([
+            Constraint::Length(2),
+            Constraint::Min(0),
+            Constraint::Length(projects_height),
+        ])
+        .areas(inner);
+
+        let summary = match distribution.busiest() {
+            Some(hour) => i18n::fill(
+                "{} entries, busiest hour {}",
+                &[
+                    &distribution.total().to_string(),
+                    &format!("{hour:02}:00 - {hour:02}:59"),
+                ],
+            ),
+            None => t("Nothing here yet").to_string(),
+        };
+        Paragraph::new(Line::from(vec![
+            Span::styled(
+                format!("{} ", t("Time of day")),
+                Style::default().fg(COLOR_SECONDARY).bold(),
+            ),
+            Span::styled(summary, Style::default().fg(COLOR_TERTIARY)),
+        ]))
+        .render(summary_area, buf);
+
+        let bar_width = (chart_area.width / 24).saturating_sub(1).max(1);
+        let bars: Vec<Bar> = distribution
+            .hours()
+            .iter()
+            .enumerate()
+            .map(|(hour, count)| {
+                Bar::default()
+                    .value(*count)
+                    .label(Line::from(format!("{hour:02}")))
+                    .style(Style::default().fg(COLOR_PRIMARY))
+                    .value_style(Style::default().fg(Color::Black).bg(COLOR_PRIMARY))
+            })
+            .collect();
+
+        BarChart::default()
+            .data(BarGroup::default().bars(&bars))
+            .bar_width(bar_width)
+            .bar_gap(1)
+            .label_style(Style::default().fg(COLOR_TERTIARY))
+            .render(chart_area, buf);
+
+        render_projects(
+            &projects[..listed],
+            self.sort,
+            self.colors,
+            projects_area,
+            buf,
+        );
+    }
+}
+
+/// A row per project with the sparkline of its last days and its totals, on a shared scale.
+fn render_projects(
+    projects: &[ProjectActivity],
+    sort: ProjectSort,
+    colors: &BTreeMap<String, String>,
+    area: Rect,
+    buf: &mut Buffer,
+) {
+    if projects.is_empty() {
+        return;
+    }
+    let [header_area, _, rows_area] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Length(1),
+        Constraint::Min(0),
+    ])
+    .areas(area);
+    Paragraph::new(Line::from(vec![
+        Span::styled(
+            format!(
+                "{} ",
+                i18n::fill("Projects, last {} days", &[&ACTIVITY_DAYS.to_string()])
+            ),
+            Style::default().fg(COLOR_SECONDARY).bold(),
+        ),
+        Span::styled(
+            t(match sort {
+                ProjectSort::Time => "by tracked time",
+                ProjectSort::Entries => "by entries",
+            }),
+            Style::default().fg(COLOR_TERTIARY),
+        ),
+    ]))
+    .render(header_area, buf);
+
+    let max = projects
+        .iter()
+        .flat_map(|project| project.days(sort).iter().copied())
+        .max()
+        .unwrap_or(0)
+        .max(1);
+    let name_width = projects
+        .iter()
+        .map(|project| project.name.width() + 2)
+        .max()
+        .unwrap_or(0)
+        .min(24) as u16;
+    let rows = Layout::vertical(vec![Constraint::Length(1); projects.len()]).split(rows_area);
+    for (project, row) in projects.iter().zip(rows.iter()) {
+        let [name_area, sparkline_area, totals_area] = Layout::horizontal([
+            Constraint::Length(name_width),
+            Constraint::Length(ACTIVITY_DAYS as u16 + 2),
+            Constraint::Fill(1),
+        ])
+        .areas(*row);
+        let marker = format!("{}{}", tag::PROJECT, project.name);
+        let color = tag::color(&marker, colors);
+        Paragraph::new(Span::styled(marker, Style::default().fg(color).bold()))
+            .render(name_area, buf);
+        Sparkline::default()
+            .data(project.days(sort))
+            .max(max)
+            .style(Style::default().fg(color))
+            .render(sparkline_area.inner(Margin::new(1, 0)), buf);
+        let totals = i18n::fill(
+            "{} entries, {} tracked",
+            &[
+                &project.total_entries().to_string(),
+                &focus::format(TimeDelta::seconds(
+                    project.total_tracked().try_into().unwrap_or(i64::MAX),
+                )),
+            ],
+        );
+        Paragraph::new(Span::styled(totals, Style::default().fg(COLOR_TERTIARY)))
+            .render(totals_area, buf);
+    }
+}
@@ -0,0 +1,115 @@
+use std::fmt;
+use std::fs;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+use color_eyre::{Result, eyre::eyre};
+use flate2::Compression as Level;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use serde::Serialize;
+
+use crate::settings::Compression;
+
+/// First bytes of every gzip stream.
+static GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// A file that exists but cannot be made sense of, e.g. because writing it was interrupted.
+#[derive(Debug)]
+pub struct Corrupt {
+    pub path: PathBuf,
+    message: String,
+}
+
+impl Corrupt {
+    pub fn parse(path: &Path, error: impl fmt::Display) -> Self {
+        Self {
+            path: path.to_owned(),
+            message: format!("failed to parse {}: {error}", path.display()),
+        }
+    }
+
+    fn decode(path: &Path, error: impl fmt::Display) -> Self {
+        Self {
+            path: path.to_owned(),
+            message: format!("failed to decode {}: {error}", path.display()),
+        }
+    }
+}
+
+impl fmt::Display for Corrupt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for Corrupt {}
+
+/// Moves a corrupt file out of the way to `<name>.corrupt-<timestamp>`, returning where it
+/// went.
+pub fn set_aside(path: &Path) -> Result<PathBuf> {
+    let mut name = path.file_name().unwrap_or_default().to_owned();
+    name.push(format!(
+        ".corrupt-{}",
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    ));
+    let target = path.with_file_name(name);
+    fs::rename(path, &target).map_err(|e| eyre!("failed to move {} aside: {e}", path.display()))?;
+    Ok(target)
+}
+
+/// Serializes `value` in the given encoding.
+pub fn encode<T: Serialize + ?Sized>(value: &T, compression: Compression) -> Result<Vec<u8>> {
+    match compression {
+        Compression::None => Ok(serde_json::to_vec_pretty(value)?),
+        Compression::Gzip => {
+            let mut encoder = GzEncoder::new(Vec::new(), Level::best());
+            serde_json::to_writer(&mut encoder, value)?;
+            Ok(encoder.finish()?)
+        }
+    }
+}
+
+/// Turns data written by [`encode`] back into JSON, detecting the encoding by its magic bytes.
+pub fn decode(data: &[u8]) -> Result<String> {
+    if data.starts_with(&GZIP_MAGIC) {
+        let mut json = String::new();
+        GzDecoder::new(data).read_to_string(&mut json)?;
+        Ok(json)
+    } else {
+        Ok(String::from_utf8(data.to_vec())?)
+    }
+}
+
+pub fn read(path: &Path) -> Result<String> {
+    let data = fs::read(path).map_err(|e| eyre!("failed to read {}: {e}", path.display()))?;
+    Ok(decode(&data).map_err(|e| Corrupt::decode(path, e))?)
+}
+
+pub fn write<T: Serialize + ?Sized>(
+    path: &Path,
+    value: &T,
+    compression: Compression,
+) -> Result<()> {
+    replace(path, &encode(value, compression)?)
+}
+
+/// Writes `data` to `path` through a temporary file next to it that is renamed over it, so a
+/// failed write, e.g. on a full disk, leaves the previous content in place.
+pub fn replace(path: &Path, data: &[u8]) -> Result<()> {
+    let mut name = std::ffi::OsString::from(".");
+    name.push(path.file_name().unwrap_or_default());
+    name.push(".tmp");
+    let temporary = path.with_file_name(name);
+    let written = fs::File::create(&temporary)
+        .and_then(|mut file| {
+            file.write_all(data)?;
+            file.sync_all()
+        })
+        .and_then(|()| fs::rename(&temporary, path));
+    if let Err(e) = written {
+        let _ = fs::remove_file(&temporary);
+        return Err(eyre!("failed to write {}: {e}", path.display()));
+    }
+    Ok(())
+}
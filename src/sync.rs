@@ -0,0 +1,160 @@
+use chrono::Utc;
+use color_eyre::{Result, eyre::eyre};
+use hmac::{Hmac, KeyInit, Mac};
+use sha2::{Digest, Sha256};
+use ureq::Agent;
+
+use crate::App;
+use crate::clipboard::base64;
+use crate::merge::MergeReport;
+use crate::settings::Remote;
+use crate::storage;
+
+/// Pulls the remote copy of the log, merges it into `app`, saves and pushes the result back.
+pub fn run(app: &mut App, remote: &Remote) -> Result<MergeReport> {
+    let agent: Agent = Agent::config_builder()
+        .http_status_as_error(false)
+        .build()
+        .into();
+
+    // the remote gets the whole log, not just the months loaded so far
+    app.load_all()?;
+    let report = match pull(&agent, remote)? {
+        Some(data) => app.merge(serde_json::from_str(&data)?)?,
+        None => MergeReport::default(),
+    };
+    app.save()?;
+    push(
+        &agent,
+        remote,
+        storage::encode(app, app.settings.compression)?,
+    )?;
+    Ok(report)
+}
+
+fn pull(agent: &Agent, remote: &Remote) -> Result<Option<String>> {
+    let (url, headers) = request("GET", remote, &[])?;
+    let mut request = agent.get(&url);
+    for (name, value) in headers {
+        request = request.header(name, value);
+    }
+    let mut response = request.call()?;
+    match response.status().as_u16() {
+        200 => Ok(Some(storage::decode(&response.body_mut().read_to_vec()?)?)),
+        404 => Ok(None),
+        status => Err(eyre!("failed to download {url}: HTTP {status}")),
+    }
+}
+
+fn push(agent: &Agent, remote: &Remote, data: Vec<u8>) -> Result<()> {
+    let (url, headers) = request("PUT", remote, &data)?;
+    let mut request = agent.put(&url);
+    for (name, value) in headers {
+        request = request.header(name, value);
+    }
+    let response = request.send(&data[..])?;
+    match response.status().as_u16() {
+        200..=299 => Ok(()),
+        status => Err(eyre!("failed to upload {url}: HTTP {status}")),
+    }
+}
+
+/// Resolves the URL and authentication headers for a request with `payload` against `remote`.
+fn request(
+    method: &str,
+    remote: &Remote,
+    payload: &[u8],
+) -> Result<(String, Vec<(String, String)>)> {
+    match remote {
+        Remote::Webdav {
+            url,
+            username,
+            password,
+        } => {
+            let mut headers = Vec::new();
+            if let Some(username) = username {
+                let credentials = format!("{username}:{}", password.as_deref().unwrap_or_default());
+                headers.push((
+                    "Authorization".to_string(),
+                    format!("Basic {}", base64(credentials.as_bytes())),
+                ));
+            }
+            Ok((url.clone(), headers))
+        }
+        Remote::S3 {
+            endpoint,
+            region,
+            bucket,
+            key,
+            access_key,
+            secret_key,
+        } => {
+            let endpoint = endpoint.trim_end_matches('/');
+            let host = endpoint
+                .split_once("://")
+                .map_or(endpoint, |(_, rest)| rest)
+                .split('/')
+                .next()
+                .filter(|h| !h.is_empty())
+                .ok_or_else(|| eyre!("invalid S3 endpoint {endpoint}"))?;
+            let path = format!("/{bucket}/{}", uri_encode(key));
+
+            let now = Utc::now();
+            let date = now.format("%Y%m%d").to_string();
+            let timestamp = now.format("%Y%m%dT%H%M%SZ").to_string();
+            let payload_hash = hex(&Sha256::digest(payload));
+            let scope = format!("{date}/{region}/s3/aws4_request");
+            let signed_headers = "host;x-amz-content-sha256;x-amz-date";
+
+            let canonical_request = format!(
+                "{method}\n{path}\n\nhost:{host}\nx-amz-content-sha256:{payload_hash}\nx-amz-date:{timestamp}\n\n{signed_headers}\n{payload_hash}"
+            );
+            let string_to_sign = format!(
+                "AWS4-HMAC-SHA256\n{timestamp}\n{scope}\n{}",
+                hex(&Sha256::digest(canonical_request.as_bytes()))
+            );
+
+            let mut signing_key = format!("AWS4{secret_key}").into_bytes();
+            for part in [date.as_str(), region, "s3", "aws4_request"] {
+                signing_key = hmac(&signing_key, part.as_bytes())?;
+            }
+            let signature = hex(&hmac(&signing_key, string_to_sign.as_bytes())?);
+
+            Ok((
+                format!("{endpoint}{path}"),
+                vec![
+                    ("x-amz-date".to_string(), timestamp),
+                    ("x-amz-content-sha256".to_string(), payload_hash),
+                    (
+                        "Authorization".to_string(),
+                        format!(
+                            "AWS4-HMAC-SHA256 Credential={access_key}/{scope}, SignedHeaders={signed_headers}, Signature={signature}"
+                        ),
+                    ),
+                ],
+            ))
+        }
+    }
+}
+
+fn hmac(key: &[u8], data: &[u8]) -> Result<Vec<u8>> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).map_err(|e| eyre!(e.to_string()))?;
+    mac.update(data);
+    Ok(mac.finalize().into_bytes().to_vec())
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// Percent-encodes an S3 object key as required by SigV4, keeping `/` separators.
+fn uri_encode(s: &str) -> String {
+    s.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'/' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{b:02X}"),
+        })
+        .collect()
+}
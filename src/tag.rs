@@ -0,0 +1,98 @@
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use ratatui::style::Color;
+
+pub const TAG: char = '#';
+pub const PROJECT: char = '+';
+pub const PERSON: char = '@';
+
+const MAX_COMPLETIONS: usize = 8;
+
+static PALETTE: [Color; 9] = [
+    Color::Rgb(255, 82, 82),
+    Color::Rgb(255, 121, 63),
+    Color::Rgb(255, 177, 66),
+    Color::Rgb(255, 218, 121),
+    Color::Rgb(120, 224, 143),
+    Color::Rgb(51, 217, 178),
+    Color::Rgb(52, 172, 224),
+    Color::Rgb(112, 111, 211),
+    Color::Rgb(247, 143, 179),
+];
+
+/// Collects every `<prefix>name` marker in `content`, in order of first appearance.
+pub fn markers(content: &str, prefix: char) -> Vec<String> {
+    let mut found: Vec<String> = Vec::new();
+    for word in content.split_whitespace() {
+        let Some(name) = word.strip_prefix(prefix) else {
+            continue;
+        };
+        let name = name.trim_end_matches(|c: char| !(c.is_alphanumeric() || c == '_'));
+        if name.is_empty()
+            || !name
+                .chars()
+                .all(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | '/' | '.'))
+        {
+            continue;
+        }
+        if !found.iter().any(|f| f == name) {
+            found.push(name.to_owned());
+        }
+    }
+    found
+}
+
+/// Markers with `prefix` that start with `partial`, in the order they first appear in
+/// `contents`, so passing the most recent entries first ranks by recency.
+pub fn complete<I, S>(contents: I, prefix: char, partial: &str) -> Vec<String>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let partial = partial.to_lowercase();
+    let mut found: Vec<String> = Vec::new();
+    for content in contents {
+        for name in markers(content.as_ref(), prefix) {
+            let lower = name.to_lowercase();
+            if lower.starts_with(&partial) && lower != partial && !found.contains(&name) {
+                found.push(name);
+            }
+        }
+        if found.len() >= MAX_COMPLETIONS {
+            break;
+        }
+    }
+    found.truncate(MAX_COMPLETIONS);
+    found
+}
+
+/// Appends the tags and projects of `from` that `to` doesn't mention yet.
+pub fn inherit(from: &str, to: &str) -> String {
+    let mut to = to.to_owned();
+    for prefix in [PROJECT, TAG] {
+        let existing = markers(&to, prefix);
+        for name in markers(from, prefix) {
+            if !existing.contains(&name) {
+                to.push_str(&format!(" {prefix}{name}"));
+            }
+        }
+    }
+    to
+}
+
+/// Deterministic color for a marker such as `#meeting` or `+acme`, unless configured in
+/// `overrides`.
+pub fn color(marker: &str, overrides: &BTreeMap<String, String>) -> Color {
+    if let Some(color) = overrides.get(marker).and_then(|c| Color::from_str(c).ok()) {
+        return color;
+    }
+    // FNV-1a, so colors stay stable across builds and platforms
+    let hash = marker
+        .to_lowercase()
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325_u64, |h, b| {
+            (h ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3)
+        });
+    PALETTE[(hash % PALETTE.len() as u64) as usize]
+}
@@ -0,0 +1,127 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Clear, Paragraph, Widget, Wrap};
+use unicode_width::UnicodeWidthStr;
+
+use crate::COLOR_PRIMARY;
+
+/// How long a toast stays on screen, errors twice as long.
+static DURATION: Duration = Duration::from_secs(4);
+/// Most toasts shown at once, the oldest is dropped for a new one.
+const MAX_TOASTS: usize = 3;
+const MAX_WIDTH: u16 = 50;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Level {
+    Info,
+    Error,
+}
+
+/// Feedback on an action, shown in the bottom right corner until it expires.
+#[derive(Debug, Clone)]
+pub struct Toast {
+    pub message: String,
+    pub level: Level,
+    shown: Instant,
+}
+
+impl Toast {
+    fn expired(&self) -> bool {
+        let duration = match self.level {
+            Level::Info => DURATION,
+            Level::Error => DURATION * 2,
+        };
+        self.shown.elapsed() >= duration
+    }
+}
+
+/// The toasts on screen, oldest first.
+#[derive(Debug, Default, Clone)]
+pub struct Toasts(VecDeque<Toast>);
+
+impl Toasts {
+    pub fn info(&mut self, message: impl Into<String>) {
+        self.push(message.into(), Level::Info);
+    }
+
+    pub fn error(&mut self, message: impl Into<String>) {
+        self.push(message.into(), Level::Error);
+    }
+
+    fn push(&mut self, message: String, level: Level) {
+        if self.0.len() == MAX_TOASTS {
+            self.0.pop_front();
+        }
+        self.0.push_back(Toast {
+            message,
+            level,
+            shown: Instant::now(),
+        });
+    }
+
+    /// Removes the toasts that were shown long enough, called on every tick. Returns whether
+    /// any were removed.
+    pub fn expire(&mut self) -> bool {
+        let before = self.0.len();
+        self.0.retain(|toast| !toast.expired());
+        self.0.len() != before
+    }
+
+    /// Removes the errors, e.g. to report them where no toasts are shown.
+    pub fn take_errors(&mut self) -> Vec<String> {
+        let (errors, rest) = self
+            .0
+            .drain(..)
+            .partition(|toast| toast.level == Level::Error);
+        self.0 = rest;
+        errors.into_iter().map(|toast| toast.message).collect()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Toast> {
+        self.0.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl Widget for &Toasts {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        // stacked upwards from above the bottom border, the newest at the bottom
+        let mut bottom = area.bottom().saturating_sub(1);
+        for toast in self.0.iter().rev() {
+            let width = (toast.message.width() as u16 + 4)
+                .min(MAX_WIDTH)
+                .min(area.width);
+            let lines = (toast.message.width() as u16).div_ceil(width.saturating_sub(4).max(1));
+            let height = lines.max(1) + 2;
+            if bottom < area.y + height {
+                break;
+            }
+            let toast_area = Rect::new(
+                area.right().saturating_sub(width + 1),
+                bottom - height,
+                width,
+                height,
+            );
+            let color = match toast.level {
+                Level::Info => COLOR_PRIMARY,
+                Level::Error => Color::LightRed,
+            };
+            Clear.render(toast_area, buf);
+            Paragraph::new(toast.message.as_str())
+                .wrap(Wrap { trim: true })
+                .style(Style::default().fg(Color::White))
+                .block(
+                    Block::bordered()
+                        .border_style(Style::default().fg(color))
+                        .padding(ratatui::widgets::Padding::horizontal(1)),
+                )
+                .render(toast_area, buf);
+            bottom -= height;
+        }
+    }
+}
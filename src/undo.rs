@@ -0,0 +1,69 @@
+use std::{fs, path::PathBuf};
+
+use chrono::{DateTime, Local};
+use color_eyre::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::App;
+use crate::log::Item;
+use crate::storage::{self, Corrupt};
+
+/// Number of operations kept on disk. Older ones are dropped first.
+pub const MAX_ENTRIES: usize = 50;
+
+/// State of an entry before a destructive operation touched it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Change {
+    Removed(Item),
+    Updated(Item),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Entry {
+    pub at: DateTime<Local>,
+    pub changes: Vec<Change>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Journal {
+    entries: Vec<Entry>,
+}
+
+impl Journal {
+    pub fn path() -> PathBuf {
+        App::config_path().with_file_name("undo.json")
+    }
+
+    pub fn load() -> Result<Self> {
+        let path = Self::path();
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(&path)?;
+        Ok(serde_json::from_str(&content).map_err(|e| Corrupt::parse(&path, e))?)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        storage::replace(
+            &Self::path(),
+            serde_json::to_string_pretty(self)?.as_bytes(),
+        )
+    }
+
+    pub fn record(&mut self, changes: Vec<Change>) {
+        if changes.is_empty() {
+            return;
+        }
+        self.entries.push(Entry {
+            at: Local::now(),
+            changes,
+        });
+        if self.entries.len() > MAX_ENTRIES {
+            self.entries.drain(..self.entries.len() - MAX_ENTRIES);
+        }
+    }
+
+    pub fn pop(&mut self) -> Option<Entry> {
+        self.entries.pop()
+    }
+}
@@ -0,0 +1,304 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use chrono::{Local, NaiveDate, NaiveTime, TimeDelta};
+use color_eyre::{Result, eyre::eyre};
+use regex::Regex;
+
+use crate::export::{self, Period};
+use crate::log::Item;
+use crate::query::Query;
+use crate::signal::Signals;
+use crate::{App, audit, focus};
+
+/// How often `--watch` looks for edited files.
+static POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Heads the section of an entry, e.g. `## 09:30 (0:45:00) <!-- lw:<id> -->`.
+#[allow(clippy::expect_used)]
+static HEADING: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^## (\d{2}:\d{2})\b(.*)$").expect("the pattern is valid"));
+/// A line of an entry that would read as a heading, written with a `\` in front for each
+/// it already had plus one.
+#[allow(clippy::expect_used)]
+static ESCAPED: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\\*## \d{2}:\d{2}\b").expect("the pattern is valid"));
+#[allow(clippy::expect_used)]
+static MARKER: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"<!-- lw:([0-9a-f-]{36}) -->").expect("the pattern is valid"));
+
+/// An entry as written to a day file, `id` is `None` for one added there.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Section {
+    time: NaiveTime,
+    id: Option<String>,
+    content: String,
+}
+
+/// Writes a Markdown file per day of `period` to `dir`, e.g. `2026-10-14.md`, with the
+/// tags, projects, people and tracked time of the day as front matter. Files that would not
+/// change are left alone. Returns the number of files written.
+pub fn export(logs: &[Item], period: Period, dir: &Path) -> Result<usize> {
+    fs::create_dir_all(dir).map_err(|e| eyre!("failed to create {}: {e}", dir.display()))?;
+    let start = period.start(Local::now().date_naive());
+    let mut days: BTreeMap<NaiveDate, Vec<&Item>> = BTreeMap::new();
+    for item in logs {
+        let day = item.created().date_naive();
+        if start.is_none_or(|start| day >= start) {
+            days.entry(day).or_default().push(item);
+        }
+    }
+    let mut written = 0;
+    for (day, items) in days {
+        if write(dir, day, &items)? {
+            written += 1;
+        }
+    }
+    Ok(written)
+}
+
+/// Writes the file of `day` unless it already has this content, returning whether it did.
+fn write(dir: &Path, day: NaiveDate, items: &[&Item]) -> Result<bool> {
+    let path = file(dir, day);
+    let content = render(day, items);
+    if fs::read_to_string(&path).is_ok_and(|existing| existing == content) {
+        return Ok(false);
+    }
+    fs::write(&path, content).map_err(|e| eyre!("failed to write {}: {e}", path.display()))?;
+    Ok(true)
+}
+
+fn file(dir: &Path, day: NaiveDate) -> PathBuf {
+    dir.join(format!("{day}.md"))
+}
+
+fn render(day: NaiveDate, items: &[&Item]) -> String {
+    let mut items = items.to_vec();
+    items.sort_by_key(|item| item.created());
+    let collect = |markers: fn(&Item) -> Vec<String>| {
+        let mut all: Vec<String> = items.iter().flat_map(|item| markers(item)).collect();
+        all.sort();
+        all.dedup();
+        serde_json::to_string(&all).unwrap_or_default()
+    };
+    let tracked = items
+        .iter()
+        .map(|item| item.tracked())
+        .fold(TimeDelta::zero(), |sum, tracked| {
+            sum.checked_add(&tracked).unwrap_or(TimeDelta::MAX)
+        });
+
+    let mut page = String::new();
+    let _ = write!(
+        page,
+        "---\ndate: {day}\ntags: {}\nprojects: {}\npeople: {}\nentries: {}\ntracked: \"{}\"\n---\n",
+        collect(Item::tags),
+        collect(Item::projects),
+        collect(Item::people),
+        items.len(),
+        focus::format(tracked),
+    );
+    for item in items {
+        let _ = write!(page, "\n## {}", item.created().format("%H:%M"));
+        if item.tracked() > TimeDelta::zero() {
+            let _ = write!(page, " ({})", focus::format(item.tracked()));
+        }
+        let _ = writeln!(page, " <!-- lw:{} -->\n", item.id());
+        for line in item.content().trim_end().lines() {
+            let escape = if ESCAPED.is_match(line) { "\\" } else { "" };
+            let _ = writeln!(page, "{escape}{line}");
+        }
+    }
+    page
+}
+
+/// The sections of a day file. Anything before the first `## HH:MM` heading, like the front
+/// matter, is skipped, and the lines escaped by [`render`] are restored.
+fn parse(text: &str) -> Vec<Section> {
+    let mut sections: Vec<Section> = Vec::new();
+    for line in text.lines() {
+        if let Some(heading) = HEADING.captures(line)
+            && let Ok(time) = NaiveTime::parse_from_str(&heading[1], "%H:%M")
+        {
+            sections.push(Section {
+                time,
+                id: MARKER
+                    .captures(&heading[2])
+                    .map(|marker| marker[1].to_owned()),
+                content: String::new(),
+            });
+        } else if let Some(section) = sections.last_mut() {
+            let line = match line.strip_prefix('\\') {
+                Some(unescaped) if ESCAPED.is_match(line) => unescaped,
+                _ => line,
+            };
+            section.content.push_str(line);
+            section.content.push('\n');
+        }
+    }
+    for section in &mut sections {
+        section.content = section.content.trim().to_owned();
+    }
+    sections
+}
+
+/// What importing a day file changed.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+struct Imported {
+    updated: usize,
+    added: usize,
+    /// Why sections were left out, e.g. a time skipped by a change to daylight saving time.
+    skipped: Vec<String>,
+}
+
+/// Applies the edits of the file of `day` to the log: an edited section updates its entry,
+/// a section without an id becomes a new entry at its time. Removing a section or emptying
+/// it leaves the entry alone.
+fn import(app: &mut App, day: NaiveDate, text: &str) -> Result<Imported> {
+    let mut imported = Imported::default();
+    for section in parse(text) {
+        if section.content.is_empty() {
+            continue;
+        }
+        match section.id {
+            Some(id) => {
+                let Some(item) = app.logs.iter().find(|i| i.id() == id) else {
+                    continue;
+                };
+                if item.content().trim() != section.content {
+                    app.update(id, section.content);
+                    imported.updated += 1;
+                }
+            }
+            None => {
+                let Some(at) = day
+                    .and_time(section.time)
+                    .and_local_timezone(Local)
+                    .earliest()
+                else {
+                    imported
+                        .skipped
+                        .push(format!("{day} {} does not exist here", section.time));
+                    continue;
+                };
+                let mut item = Item::from(section.content);
+                item.backdate(at);
+                app.load_month(&item)?;
+                app.add(item);
+                imported.added += 1;
+            }
+        }
+    }
+    Ok(imported)
+}
+
+/// The day a file in the vault belongs to, from a name like `2026-10-14.md`.
+fn day_of(path: &Path) -> Option<NaiveDate> {
+    if path.extension().is_none_or(|e| e != "md") {
+        return None;
+    }
+    path.file_stem()?.to_str()?.parse().ok()
+}
+
+/// Modification times of the day files in `dir`.
+fn scan(dir: &Path) -> Result<BTreeMap<PathBuf, SystemTime>> {
+    let mut files = BTreeMap::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        // a file removed since it was listed is gone from the next scan as well
+        if day_of(&path).is_some()
+            && let Some(time) = modified(&path)
+        {
+            files.insert(path, time);
+        }
+    }
+    Ok(files)
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// Keeps `dir` and the log in step until terminated: edits of the day files are imported,
+/// and the files of the entries matching `query` are written again when another process
+/// saved the log.
+pub fn watch(app: &mut App, period: Period, query: Option<&Query>, dir: &Path) -> Result<()> {
+    let signals = Signals::register()?;
+    let config = App::config_path();
+    let mut saved = modified(config);
+    let mut files = scan(dir)?;
+    println!("watching {} for edits", dir.display());
+    while !signals.terminated() {
+        thread::sleep(POLL_INTERVAL);
+        if modified(config) != saved {
+            *app = App::new(config.to_owned())?;
+            app.load_all()?;
+            export(&export::select(&app.logs, query), period, dir)?;
+            saved = modified(config);
+            files = scan(dir)?;
+            continue;
+        }
+        let current = scan(dir)?;
+        for (path, time) in &current {
+            if files.insert(path.clone(), *time) == Some(*time) {
+                continue;
+            }
+            let Some(day) = day_of(path) else {
+                continue;
+            };
+            // a file that cannot be imported is reported and tried again once it changes
+            let text = match fs::read_to_string(path) {
+                Ok(text) => text,
+                Err(e) => {
+                    eprintln!("failed to read {}: {e}", path.display());
+                    continue;
+                }
+            };
+            app.audit.context = Some(audit::Kind::Import);
+            let imported = import(app, day, &text);
+            app.audit.context = None;
+            let imported = match imported {
+                Ok(imported) => imported,
+                Err(e) => {
+                    eprintln!("{}: {e}", path.display());
+                    continue;
+                }
+            };
+            for reason in &imported.skipped {
+                eprintln!("{}: skipped {reason}", path.display());
+            }
+            if imported.updated == 0 && imported.added == 0 {
+                continue;
+            }
+            app.save()?;
+            saved = modified(config);
+            println!(
+                "{}: {} updated, {} added",
+                path.display(),
+                imported.updated,
+                imported.added
+            );
+            // new entries need their ids in the file, so they are not added again
+            if imported.added > 0 {
+                let items: Vec<&Item> = app
+                    .logs
+                    .iter()
+                    .filter(|i| i.created().date_naive() == day)
+                    .filter(|i| query.is_none_or(|query| query.matches(i)))
+                    .collect();
+                if let Err(e) = write(dir, day, &items) {
+                    eprintln!("{e}");
+                }
+                if let Some(time) = modified(path) {
+                    files.insert(path.clone(), time);
+                }
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,223 @@
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use chrono::{Datelike, Days, Local, NaiveDate, Weekday};
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Paragraph, Widget, Wrap};
+use ratatui::{style::Color, symbols::border, text::Line};
+
+use crate::goal::Goal;
+use crate::i18n::{self, t};
+use crate::log::Item;
+use crate::{COLOR_PRIMARY, COLOR_SECONDARY, COLOR_TERTIARY, COLOR_TERTIARY_DARK, editor};
+
+/// Height of an entry card, including its border.
+const CARD_HEIGHT: u16 = 4;
+
+/// First day of the week as days from Monday, Monday unless the settings say otherwise.
+static FIRST_DAY: AtomicU8 = AtomicU8::new(0);
+
+/// Makes weeks start on `day` from now on.
+pub fn set_first_day(day: Weekday) {
+    FIRST_DAY.store(day.num_days_from_monday() as u8, Ordering::Relaxed);
+}
+
+pub fn first_day() -> Weekday {
+    Weekday::try_from(FIRST_DAY.load(Ordering::Relaxed)).unwrap_or(Weekday::Mon)
+}
+
+/// The week shown in the week view and the card selected in it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cursor {
+    /// First day of the week.
+    pub start: NaiveDate,
+    /// Selected column, 0 for the first day.
+    pub day: usize,
+    /// Selected card within the column.
+    pub row: usize,
+}
+
+impl Default for Cursor {
+    fn default() -> Self {
+        Self::on(Local::now().date_naive())
+    }
+}
+
+impl Cursor {
+    /// The week containing `day`, with `day` selected.
+    pub fn on(day: NaiveDate) -> Self {
+        let offset = day.weekday().days_since(first_day());
+        Self {
+            start: day - Days::new(offset.into()),
+            day: offset as usize,
+            row: 0,
+        }
+    }
+
+    pub fn date(&self) -> NaiveDate {
+        self.start + Days::new(self.day as u64)
+    }
+
+    pub fn end(&self) -> NaiveDate {
+        self.start + Days::new(6)
+    }
+
+    /// The ISO week number of the week, taken from its middle so weeks starting on Sunday get
+    /// the number of the Monday after.
+    pub fn number(&self) -> u32 {
+        (self.start + Days::new(3)).iso_week().week()
+    }
+
+    /// Moves the selection by `days`, continuing into the neighbouring weeks.
+    pub fn shift(&mut self, days: i64) {
+        let date = self.date() + chrono::Duration::days(days);
+        *self = Self::on(date);
+    }
+}
+
+/// Entries of every day of the week starting at `start`, oldest first.
+pub fn days(logs: &[Item], start: NaiveDate) -> [Vec<&Item>; 7] {
+    let mut days: [Vec<&Item>; 7] = Default::default();
+    for item in logs {
+        let day = item.created().date_naive();
+        if let Ok(offset) = usize::try_from((day - start).num_days())
+            && offset < 7
+        {
+            days[offset].push(item);
+        }
+    }
+    for entries in &mut days {
+        entries.sort_by_key(|item| item.created());
+    }
+    days
+}
+
+pub struct WeekView<'a> {
+    pub logs: &'a [Item],
+    pub cursor: Cursor,
+    /// Days that fell short of it are highlighted.
+    pub goal: Option<&'a Goal>,
+}
+
+impl Widget for WeekView<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let keys = |keys| {
+            Span::styled(
+                keys,
+                Style::default()
+                    .fg(COLOR_PRIMARY)
+                    .add_modifier(Modifier::BOLD),
+            )
+        };
+        let block = Block::bordered()
+            .title(
+                Line::from(Span::styled(
+                    format!(
+                        " {} ",
+                        i18n::fill(
+                            "Week {} · {} – {}",
+                            &[
+                                &self.cursor.number().to_string(),
+                                &i18n::short_date(self.cursor.start),
+                                &i18n::long_date(self.cursor.end()),
+                            ]
+                        )
+                    ),
+                    Style::default().fg(COLOR_PRIMARY).bold(),
+                ))
+                .centered(),
+            )
+            .title_bottom(
+                Line::from(vec![
+                    Span::raw(format!(" {} ", t("Move"))),
+                    keys("<h> <j> <k> <l>"),
+                    Span::raw(format!(" {} ", t("Open"))),
+                    keys("<Enter>"),
+                    Span::raw(format!(" {} ", t("Week"))),
+                    keys("<H> | <L>"),
+                    Span::raw(format!(" {} ", t("Today"))),
+                    keys("<t>"),
+                    Span::raw(format!(" {} ", t("Go to"))),
+                    keys("<g>"),
+                    Span::raw(format!(" {} ", t("Back"))),
+                    keys("<w> | <q> | <ESC>"),
+                ])
+                .centered(),
+            )
+            .title_style(Color::White)
+            .border_set(border::THICK)
+            .border_style(Color::White);
+
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let today = Local::now().date_naive();
+        let columns = Layout::horizontal([Constraint::Ratio(1, 7); 7]).split(inner);
+        for (offset, (entries, column)) in days(self.logs, self.cursor.start)
+            .iter()
+            .zip(columns.iter())
+            .enumerate()
+        {
+            let date = self.cursor.start + Days::new(offset as u64);
+            let selected_day = offset == self.cursor.day;
+            let [header, cards] =
+                Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(*column);
+
+            let missed = self.goal.is_some_and(|goal| goal.missed(self.logs, date));
+            let color = match (date == today, selected_day) {
+                (_, true) => COLOR_PRIMARY,
+                (true, false) => COLOR_SECONDARY,
+                (false, false) if missed => Color::LightRed,
+                (false, false) => COLOR_TERTIARY,
+            };
+            Paragraph::new(Line::from(Span::styled(
+                format!(
+                    "{} {} ({}){}",
+                    i18n::weekday(date.weekday()),
+                    date.format("%d"),
+                    entries.len(),
+                    if missed { " ✗" } else { "" }
+                ),
+                Style::default().fg(color).bold(),
+            )))
+            .centered()
+            .render(header, buf);
+
+            // keep the selected card in view when a day has more entries than fit
+            let fitting = usize::from((cards.height / CARD_HEIGHT).max(1));
+            let skip = if selected_day {
+                self.cursor.row.saturating_sub(fitting - 1)
+            } else {
+                0
+            };
+            for (row, item) in entries.iter().enumerate().skip(skip).take(fitting) {
+                let y = cards.y + (row - skip) as u16 * CARD_HEIGHT;
+                let card = Rect::new(cards.x, y, cards.width, CARD_HEIGHT).intersection(cards);
+                let selected = selected_day && row == self.cursor.row;
+                let border = if selected {
+                    COLOR_PRIMARY
+                } else {
+                    COLOR_TERTIARY_DARK
+                };
+                let content = item.content();
+                let (line, _) = editor::first_line(&content);
+                Paragraph::new(line)
+                    .wrap(Wrap { trim: true })
+                    .block(
+                        Block::bordered()
+                            .title(Span::styled(
+                                item.created().format("%H:%M").to_string(),
+                                Style::default().fg(COLOR_SECONDARY),
+                            ))
+                            .title(Line::from(item.badges()).right_aligned())
+                            .border_style(border),
+                    )
+                    .style(if selected {
+                        Style::default().fg(Color::White).bold()
+                    } else {
+                        Style::default().fg(COLOR_TERTIARY)
+                    })
+                    .render(card, buf);
+            }
+        }
+    }
+}
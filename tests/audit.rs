@@ -0,0 +1,36 @@
+//! Saves a log on disk, which takes a test binary of its own since the directory of lw is
+//! resolved once per process.
+
+use std::{env, fs};
+
+use lw::App;
+use lw::audit::{self, Kind};
+use lw::log::Item;
+
+#[test]
+fn failed_save_keeps_its_audit_records() {
+    let dir = env::temp_dir().join(format!("lw-audit-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    // SAFETY: set before anything reads the environment, and this is the only test here
+    unsafe { env::set_var(lw::paths::OVERRIDE, &dir) };
+
+    let mut app = App::new(App::config_path().to_owned()).expect("the log is created");
+    let item = Item::from("Audited entry");
+    let id = item.id();
+    app.add(item);
+
+    // a file in place of the monthly shards makes the save fail
+    fs::write(dir.join("logs"), "").expect("the directory is writable");
+    assert!(app.save().is_err());
+    fs::remove_file(dir.join("logs")).expect("the file exists");
+    app.save().expect("the retried save succeeds");
+
+    let records = audit::read(Some(&id)).expect("the audit log reads");
+    assert_eq!(records.len(), 1, "{records:?}");
+    assert_eq!(records[0].kind, Kind::Add);
+    assert_eq!(
+        records[0].after.as_ref().map(Item::content).as_deref(),
+        Some("Audited entry")
+    );
+    let _ = fs::remove_dir_all(&dir);
+}
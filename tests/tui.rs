@@ -0,0 +1,370 @@
+//! Drives the TUI with synthetic key events and compares what it renders with the snapshots in
+//! `tests/snapshots`. Run with `LW_UPDATE_SNAPSHOTS=1` to accept changed output.
+
+use std::fs;
+use std::path::PathBuf;
+
+use chrono::{Local, TimeZone, Weekday};
+use lw::App;
+use lw::i18n::Language;
+use lw::log::Item;
+use lw::week;
+use ratatui::Terminal;
+use ratatui::backend::TestBackend;
+use ratatui::crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+
+const WIDTH: u16 = 100;
+const HEIGHT: u16 = 20;
+
+fn item(id: u8, content: &str, day: u32, hour: u32) -> Item {
+    let at = Local
+        .with_ymd_and_hms(2026, 3, day, hour, 0, 0)
+        .single()
+        .expect("the time exists");
+    serde_json::from_value(serde_json::json!({
+        "id": format!("{id:x}0000000-0000-4000-8000-000000000000"),
+        "content": content,
+        "created": at,
+        "modified": at,
+    }))
+    .expect("the entry deserializes")
+}
+
+fn app() -> App {
+    Language::En.set();
+    week::set_first_day(Weekday::Mon);
+    App::in_memory(vec![
+        item(1, "Reviewed the release notes #release", 2, 9),
+        item(2, "Standup with @anna about +website", 3, 10),
+        item(
+            3,
+            "Fixed the login redirect\nIt looped when the session expired.",
+            4,
+            14,
+        ),
+        item(4, "Planned the next sprint #planning", 5, 16),
+    ])
+}
+
+fn press(app: &mut App, code: KeyCode) {
+    app.handle_event(Event::Key(KeyEvent::new(code, KeyModifiers::NONE)));
+}
+
+fn ctrl(app: &mut App, c: char) {
+    app.handle_event(Event::Key(KeyEvent::new(
+        KeyCode::Char(c),
+        KeyModifiers::CONTROL,
+    )));
+}
+
+fn keys(app: &mut App, keys: &str) {
+    for c in keys.chars() {
+        press(app, KeyCode::Char(c));
+    }
+}
+
+fn render(app: &mut App) -> String {
+    render_at(app, WIDTH, HEIGHT)
+}
+
+/// The text of the rendered screen, one line per row without trailing blanks.
+fn render_at(app: &mut App, width: u16, height: u16) -> String {
+    let mut terminal = Terminal::new(TestBackend::new(width, height)).expect("the backend works");
+    terminal
+        .draw(|frame| app.draw(frame))
+        .expect("the frame renders");
+    let buffer = terminal.backend().buffer();
+    (0..buffer.area.height)
+        .map(|y| {
+            let line: String = (0..buffer.area.width)
+                .map(|x| buffer[(x, y)].symbol())
+                .collect();
+            format!("{}\n", line.trim_end())
+        })
+        .collect()
+}
+
+fn assert_snapshot(name: &str, actual: &str) {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/snapshots")
+        .join(format!("{name}.txt"));
+    if std::env::var_os("LW_UPDATE_SNAPSHOTS").is_some() {
+        fs::write(&path, actual).expect("the snapshot is writable");
+        return;
+    }
+    let expected = fs::read_to_string(&path).unwrap_or_else(|_| {
+        panic!(
+            "no snapshot at {}, run with LW_UPDATE_SNAPSHOTS=1 to create it",
+            path.display()
+        )
+    });
+    assert!(
+        expected == actual,
+        "{name} differs from its snapshot\n--- expected\n{expected}\n--- actual\n{actual}"
+    );
+}
+
+#[test]
+fn main_view() {
+    let mut app = app();
+    assert_snapshot("main_view", &render(&mut app));
+}
+
+#[test]
+fn main_view_moves_the_selection() {
+    let mut app = app();
+    keys(&mut app, "jj");
+    assert_snapshot("main_view_selection", &render(&mut app));
+}
+
+#[test]
+fn main_view_densities() {
+    let mut app = app();
+    keys(&mut app, "z");
+    assert_snapshot("main_view_spacious", &render(&mut app));
+    keys(&mut app, "z");
+    assert_snapshot("main_view_compact", &render(&mut app));
+}
+
+#[test]
+fn every_badge_fits() {
+    let at = Local
+        .with_ymd_and_hms(2026, 3, 6, 9, 0, 0)
+        .single()
+        .expect("the time exists");
+    let badged: Item = serde_json::from_value(serde_json::json!({
+        "id": "50000000-0000-4000-8000-000000000000",
+        "content": "Shipped the release",
+        "created": at,
+        "modified": at,
+        "daily": true,
+        "pinned": true,
+        "links": ["10000000-0000-4000-8000-000000000000"],
+        "tracked": 60,
+        "attachments": [{"file": "notes.txt"}],
+        "billable": true,
+        "done": true,
+    }))
+    .expect("the entry deserializes");
+    let badges = badged.badges();
+    let mut app = App::in_memory(vec![badged]);
+    let screen = render(&mut app);
+    // the cells hidden behind wide characters render as blanks
+    assert!(
+        screen
+            .lines()
+            .any(|line| line.replace(' ', "").contains(&badges)),
+        "{screen}"
+    );
+    assert!(screen.contains("Shipped the release"), "{screen}");
+}
+
+#[test]
+fn main_view_search() {
+    let mut app = app();
+    keys(&mut app, "/tag:release");
+    press(&mut app, KeyCode::Enter);
+    assert_snapshot("main_view_search", &render(&mut app));
+}
+
+#[test]
+fn delete_and_undo() {
+    let mut app = app();
+    keys(&mut app, "dd");
+    assert_snapshot("delete", &render(&mut app));
+    keys(&mut app, "u");
+    assert_snapshot("delete_undo", &render(&mut app));
+}
+
+#[test]
+fn edit_view() {
+    let mut app = app();
+    keys(&mut app, "jj");
+    press(&mut app, KeyCode::Enter);
+    assert_snapshot("edit_view", &render(&mut app));
+}
+
+#[test]
+fn edit_view_typing() {
+    let mut app = app();
+    press(&mut app, KeyCode::Enter);
+    press(&mut app, KeyCode::End);
+    keys(&mut app, " and the changelog");
+    // the editor without the line below it, which shows the time of the edit
+    let screen: String = render(&mut app)
+        .lines()
+        .take(10)
+        .map(|line| format!("{line}\n"))
+        .collect();
+    assert_snapshot("edit_view_typing", &screen);
+}
+
+#[test]
+fn new_entry_is_saved() {
+    let mut app = app();
+    keys(&mut app, "o");
+    keys(&mut app, "Wrote the snapshot tests #testing");
+    ctrl(&mut app, 'o');
+    assert!(!app.is_dirty());
+    // created just now, so only the content is stable
+    let screen = render(&mut app);
+    assert!(screen.contains("Wrote the snapshot tests #testing"));
+    assert!(screen.contains("item 1/5"));
+}
+
+#[test]
+fn daily_note_is_reopened() {
+    let mut app = app();
+    keys(&mut app, "t");
+    keys(&mut app, "first note");
+    ctrl(&mut app, 'o');
+    keys(&mut app, "t");
+    // headed by today's date, so only the content is stable
+    let screen = render(&mut app);
+    assert!(screen.contains("first note"));
+    assert!(
+        app.today()
+            .is_some_and(|note| note.content().ends_with("first note"))
+    );
+}
+
+#[test]
+fn paste_opens_a_new_entry() {
+    let mut app = app();
+    app.handle_event(Event::Paste("dd\r\nu".to_owned()));
+    // pasted keys are not run as commands, the entries are all still there
+    let screen = render(&mut app);
+    assert!(
+        screen.contains("│dd ") && screen.contains("│u "),
+        "{screen}"
+    );
+    press(&mut app, KeyCode::Esc);
+    assert!(render(&mut app).contains("item 1/4"));
+}
+
+#[test]
+fn held_keys_only_repeat_movement() {
+    let mut app = app();
+    let held = |code| {
+        Event::Key(KeyEvent::new_with_kind(
+            code,
+            KeyModifiers::NONE,
+            KeyEventKind::Repeat,
+        ))
+    };
+    keys(&mut app, "d");
+    app.handle_event(held(KeyCode::Char('d')));
+    app.handle_event(held(KeyCode::Char('j')));
+    assert_snapshot("main_view_selection_held", &render(&mut app));
+}
+
+#[test]
+fn small_terminal() {
+    let mut app = app();
+    assert_snapshot("small_terminal", &render_at(&mut app, 36, 8));
+    // keys are ignored until the terminal grows
+    keys(&mut app, "dd");
+    assert!(render(&mut app).contains("item 1/4"));
+
+    // except for quitting, which has no other way in raw mode
+    render_at(&mut app, 36, 8);
+    ctrl(&mut app, 'c');
+    assert!(app.exiting());
+}
+
+#[test]
+fn command_palette() {
+    let mut app = app();
+    keys(&mut app, ":sort");
+    assert_snapshot("command_palette", &render(&mut app));
+    press(&mut app, KeyCode::Enter);
+    let screen = render(&mut app);
+    assert!(!screen.contains("Commands"));
+    // sorted alphabetically instead of newest first
+    let position = |text| screen.find(text).expect("the entry is shown");
+    assert!(
+        position("Fixed the login") < position("Planned the next"),
+        "{screen}"
+    );
+}
+
+#[test]
+fn review_of_the_day() {
+    let mut app = app();
+    app.add(Item::from("Deployed the website"));
+    keys(&mut app, "Rt45m");
+    press(&mut app, KeyCode::Enter);
+    keys(&mut app, "xq");
+    let summary = app.take_summary().expect("the review ends with a summary");
+    assert!(summary.contains("- [x] "), "{summary}");
+    assert!(
+        summary.contains("Deployed the website (0:45:00)"),
+        "{summary}"
+    );
+    assert!(summary.ends_with("Tracked: 0:45:00\n"), "{summary}");
+}
+
+#[test]
+fn review_ignores_durations_out_of_range() {
+    let mut app = app();
+    app.add(Item::from("Deployed the website"));
+    for duration in [
+        "9999999999999999",
+        "99999999999999h",
+        "9999999999999h9999999999999999m",
+    ] {
+        keys(&mut app, "Rt");
+        keys(&mut app, duration);
+        press(&mut app, KeyCode::Enter);
+        press(&mut app, KeyCode::Esc);
+        keys(&mut app, "q");
+    }
+    assert!(render(&mut app).contains("Deployed the website"));
+}
+
+#[test]
+fn browsing_by_period() {
+    let mut app = app();
+    keys(&mut app, "vv");
+    let week = render(&mut app);
+    assert!(week.contains("[Week 10 · Mar 02 – Mar 08]"), "{week}");
+    assert!(week.contains("item 1/4"), "{week}");
+    keys(&mut app, "v");
+    assert_snapshot("main_view_day", &render(&mut app));
+    keys(&mut app, "[");
+    assert!(render(&mut app).contains("item 1/1"));
+    keys(&mut app, "v");
+    assert!(render(&mut app).contains("item 1/4"));
+}
+
+#[test]
+fn long_log_scrolls() {
+    Language::En.set();
+    let logs = (1..=56u32)
+        .map(|n| {
+            let mut item = Item::from(format!("Entry {n}"));
+            let hour = if n % 2 == 0 { 15 } else { 9 };
+            let at = Local.with_ymd_and_hms(2026, 3, n.div_ceil(2), hour, 0, 0);
+            item.backdate(at.single().expect("the time exists"));
+            item
+        })
+        .collect();
+    let mut app = App::in_memory(logs);
+    keys(&mut app, "G");
+    assert_snapshot("main_view_bottom", &render(&mut app));
+    keys(&mut app, "g");
+    let top = render(&mut app);
+    assert!(top.contains("item 1/56"), "{top}");
+}
+
+#[test]
+fn edited_rows_are_redrawn() {
+    let mut app = app();
+    render(&mut app);
+    app.update(
+        "10000000-0000-4000-8000-000000000000",
+        "Rewrote the release notes #release",
+    );
+    let screen = render(&mut app);
+    assert!(screen.contains("Rewrote the release notes"), "{screen}");
+}